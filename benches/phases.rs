@@ -0,0 +1,108 @@
+// Time spent in each phase of every compiled-in backend, one benchmark group
+// per backend and one id per circuit shape (word length x alphabet size).
+// Circuit and proof sizes are printed alongside, since they are what moves
+// when the circuit grows.
+//
+//     cargo bench --bench phases
+
+use criterion::measurement::WallTime;
+use criterion::{criterion_group, criterion_main, BenchmarkGroup, BenchmarkId, Criterion};
+use std::sync::Arc;
+
+use zk_wordle::{Alphabet, Dictionary, GameConfig, Guess, ProofSystem, Prover, R1csBuilder, Verifier, WordleCircuit};
+
+// Every word length the built-in dictionary covers, over the full alphabet
+fn configs() -> impl Iterator<Item = GameConfig> {
+    (4..=7).map(|word_len| GameConfig::new(word_len, Alphabet::english(), Some(6)).unwrap())
+}
+
+// Prove and verify one turn through the game API, which is what a turn costs
+// end to end, encoding and decoding the proof included
+fn bench_turn<S: ProofSystem>(group: &mut BenchmarkGroup<WallTime>, config: &GameConfig, params: Arc<S::Params>) {
+    let shape = config.shape_name();
+    let dictionary = Dictionary::embedded(config).unwrap();
+    let (hidden_word, guess) = (&dictionary.answers()[0], &dictionary.answers()[1]);
+
+    let circuit = R1csBuilder::<S::Field>::record(WordleCircuit::blank(config)).unwrap();
+    let answers = dictionary.answer_tree::<S::Field>().unwrap();
+    let hidden_word = Guess::parse(config, hidden_word).unwrap().letters().to_vec();
+    let prover = Prover::<S>::new(config, &answers, hidden_word, params.clone()).unwrap();
+    let verifier = Verifier::<S>::new(config, prover.commitment(), answers.root(), S::verifying_key(&params));
+    let guess = Guess::parse(config, guess).unwrap();
+    let (feedback, proof) = prover.prove(&guess).unwrap();
+    println!(
+        "{} {}: {} constraints, {} variables, {} public inputs, {} byte proofs",
+        S::NAME,
+        shape,
+        circuit.num_constraints(),
+        circuit.num_vars(),
+        circuit.num_inputs(),
+        proof.as_bytes().len()
+    );
+
+    group.bench_function(BenchmarkId::new("prove", &shape), |b| b.iter(|| prover.prove(&guess).unwrap()));
+    group.bench_function(BenchmarkId::new("verify", &shape), |b| {
+        b.iter(|| assert!(verifier.verify(&guess, &feedback, &proof).unwrap()))
+    });
+}
+
+#[cfg(feature = "spartan")]
+fn spartan(c: &mut Criterion) {
+    use curve25519_dalek::scalar::Scalar;
+    use libspartan::{NIZKGens, SNARKGens, SNARK};
+    use zk_wordle::{SpartanNizk, SpartanSnark};
+
+    let mut group = c.benchmark_group(SpartanSnark::NAME);
+    group.sample_size(10);
+    for config in configs() {
+        let shape = config.shape_name();
+        let circuit = R1csBuilder::<Scalar>::record(WordleCircuit::blank(&config)).unwrap();
+        let (cons, vars, inputs) = (circuit.num_constraints(), circuit.num_vars(), circuit.num_inputs());
+        let nz = circuit.num_non_zero_entries();
+        let inst = circuit.instance().unwrap();
+
+        group.bench_function(BenchmarkId::new("gens", &shape), |b| {
+            b.iter(|| SNARKGens::new(cons, vars, inputs, nz))
+        });
+        let gens = SNARKGens::new(cons, vars, inputs, nz);
+        group.bench_function(BenchmarkId::new("encode", &shape), |b| b.iter(|| SNARK::encode(&inst, &gens)));
+        bench_turn::<SpartanSnark>(&mut group, &config, Arc::new(SpartanSnark::setup(&config).unwrap()));
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group(SpartanNizk::NAME);
+    group.sample_size(10);
+    for config in configs() {
+        let circuit = R1csBuilder::<Scalar>::record(WordleCircuit::blank(&config)).unwrap();
+        let (cons, vars, inputs) = (circuit.num_constraints(), circuit.num_vars(), circuit.num_inputs());
+        group.bench_function(BenchmarkId::new("gens", config.shape_name()), |b| {
+            b.iter(|| NIZKGens::new(cons, vars, inputs))
+        });
+        bench_turn::<SpartanNizk>(&mut group, &config, Arc::new(SpartanNizk::setup(&config).unwrap()));
+    }
+    group.finish();
+}
+
+#[cfg(not(feature = "spartan"))]
+fn spartan(_: &mut Criterion) {}
+
+#[cfg(feature = "groth16")]
+fn groth16(c: &mut Criterion) {
+    use zk_wordle::Groth16;
+
+    let mut group = c.benchmark_group(Groth16::NAME);
+    group.sample_size(10);
+    for config in configs() {
+        group.bench_function(BenchmarkId::new("setup", config.shape_name()), |b| {
+            b.iter(|| Groth16::setup(&config).unwrap())
+        });
+        bench_turn::<Groth16>(&mut group, &config, Arc::new(Groth16::setup(&config).unwrap()));
+    }
+    group.finish();
+}
+
+#[cfg(not(feature = "groth16"))]
+fn groth16(_: &mut Criterion) {}
+
+criterion_group!(benches, spartan, groth16);
+criterion_main!(benches);
@@ -0,0 +1,15 @@
+// Proof decoders of every backend on arbitrary bytes: each must return an
+// error rather than panic, and stay within its allocation limit.
+//
+//     cargo +nightly fuzz run decode_proof
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use zk_wordle::{Groth16, ProofSystem, SpartanNizk, SpartanSnark};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = SpartanSnark::proof_from_bytes(data);
+    let _ = SpartanNizk::proof_from_bytes(data);
+    let _ = Groth16::proof_from_bytes(data);
+});
@@ -0,0 +1,80 @@
+// The full verify path of every backend on an untrusted statement and proof.
+// The first ten bytes pick the guess and the claimed feedback, the rest is the
+// proof. Verifier::verify must return an error or Ok(false), never panic, and
+// never accept.
+//
+//     cargo +nightly fuzz run verify_proof
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use std::panic;
+use std::sync::{Arc, OnceLock};
+use zk_wordle::{
+    Dictionary, Feedback, GameConfig, Groth16, Guess, GuessProof, LetterFeedback, ProofSystem, Prover, SpartanNizk,
+    SpartanSnark, Verifier,
+};
+
+struct Verifiers {
+    snark: Verifier<SpartanSnark>,
+    nizk: Verifier<SpartanNizk>,
+    groth16: Verifier<Groth16>,
+}
+
+fn game<S: ProofSystem>(config: &GameConfig) -> Verifier<S> {
+    let params = Arc::new(S::setup(config).unwrap());
+    let answers = Dictionary::embedded(config).unwrap().answer_tree::<S::Field>().unwrap();
+    let prover = Prover::<S>::new(config, &answers, vec![2, 17, 0, 13, 4], params.clone()).unwrap();
+    Verifier::new(config, prover.commitment(), answers.root(), S::verifying_key(&params))
+}
+
+// One game per backend for the whole run; setup is far too slow to repeat per input
+fn verifiers() -> &'static Verifiers {
+    static VERIFIERS: OnceLock<Verifiers> = OnceLock::new();
+    VERIFIERS.get_or_init(|| {
+        let config = GameConfig::default();
+        Verifiers {
+            snark: game(&config),
+            nizk: game(&config),
+            groth16: game(&config),
+        }
+    })
+}
+
+fuzz_target!(
+    init: {
+        // libfuzzer-sys aborts on every panic, including the ones the verifier
+        // catches and reports as malformed proofs. Only a panic escaping
+        // verify should count as a crash, and the harness still aborts on those.
+        let _ = panic::take_hook();
+        verifiers();
+    },
+    |data: &[u8]| {
+        let config = GameConfig::default();
+        if data.len() < 2 * config.word_len {
+            return;
+        }
+        let (statement, proof) = data.split_at(2 * config.word_len);
+        let guess = Guess::from_letters(&config, statement[..config.word_len].iter().map(|b| b % 26).collect()).unwrap();
+        let feedback = Feedback::from_letters(
+            statement[config.word_len..]
+                .iter()
+                .map(|b| match b % 3 {
+                    0 => LetterFeedback::Gray,
+                    1 => LetterFeedback::Yellow,
+                    _ => LetterFeedback::Green,
+                })
+                .collect(),
+        );
+
+        let proof = GuessProof::from_bytes(proof.to_vec());
+        let verifiers = verifiers();
+        for (name, verified) in [
+            (SpartanSnark::NAME, verifiers.snark.verify(&guess, &feedback, &proof)),
+            (SpartanNizk::NAME, verifiers.nizk.verify(&guess, &feedback, &proof)),
+            (Groth16::NAME, verifiers.groth16.verify(&guess, &feedback, &proof)),
+        ] {
+            assert!(!matches!(verified, Ok(true)), "{} accepted a fuzzed proof", name);
+        }
+    }
+);
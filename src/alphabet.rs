@@ -0,0 +1,255 @@
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::str::FromStr;
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::error::ZkWordleError;
+
+// Largest supported alphabet. Range checking a hidden letter costs one
+// constraint per letter of the alphabet.
+pub const MAX_ALPHABET_SIZE: usize = 64;
+
+const ENGLISH: &str = "abcdefghijklmnopqrstuvwxyz";
+const SPANISH: &str = "abcdefghijklmnñopqrstuvwxyz";
+const GERMAN: &str = "abcdefghijklmnopqrstuvwxyzäöüß";
+const GREEK: &str = "αβγδεζηθικλμνξοπρστυφχψω";
+
+// Spanish and Greek Wordle ignore accents; Greek also writes σ as ς at the end
+// of a word
+const SPANISH_VARIANTS: &[(&str, &str)] = &[("á", "a"), ("é", "e"), ("í", "i"), ("ó", "o"), ("ú", "u"), ("ü", "u")];
+const GREEK_VARIANTS: &[(&str, &str)] = &[
+    ("ά", "α"),
+    ("έ", "ε"),
+    ("ή", "η"),
+    ("ί", "ι"),
+    ("ϊ", "ι"),
+    ("ΐ", "ι"),
+    ("ό", "ο"),
+    ("ς", "σ"),
+    ("ύ", "υ"),
+    ("ϋ", "υ"),
+    ("ΰ", "υ"),
+    ("ώ", "ω"),
+];
+
+// The letters of a game, in the order that gives each its index in the
+// circuit. A letter is one grapheme cluster, so "ñ" is a single letter whether
+// it arrives precomposed or as "n" plus a combining tilde: all text is compared
+// lowercased and in NFC. Variants are further spellings of a letter, such as
+// accented vowels in languages whose Wordle ignores accents; words are always
+// written back with the letter itself.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Alphabet {
+    letters: Vec<String>,
+    // sorted by spelling
+    variants: Vec<(String, u8)>,
+}
+
+// Lowercase NFC, the form every letter and word is compared in
+fn normalize(text: &str) -> String {
+    text.nfc().collect::<String>().to_lowercase().nfc().collect()
+}
+
+fn invalid(reason: String) -> ZkWordleError {
+    ZkWordleError::InvalidConfig(reason)
+}
+
+impl Alphabet {
+    pub fn new<S: AsRef<str>>(letters: &[S]) -> Result<Self, ZkWordleError> {
+        Alphabet::with_variants(letters, &[] as &[(&str, &str)])
+    }
+
+    // variants pairs each extra spelling with the letter it stands for
+    pub fn with_variants<S: AsRef<str>, T: AsRef<str>>(letters: &[S], variants: &[(T, T)]) -> Result<Self, ZkWordleError> {
+        if !(2..=MAX_ALPHABET_SIZE).contains(&letters.len()) {
+            return Err(invalid(format!(
+                "the alphabet must have 2 to {} letters, not {}",
+                MAX_ALPHABET_SIZE,
+                letters.len()
+            )));
+        }
+
+        let mut alphabet = Alphabet {
+            letters: Vec::new(),
+            variants: Vec::new(),
+        };
+        let single_letter = |spelling: &str| -> Result<String, ZkWordleError> {
+            let letter = normalize(spelling);
+            if letter.graphemes(true).count() != 1 || letter.chars().any(char::is_whitespace) {
+                return Err(invalid(format!("'{}' is not a single letter", spelling)));
+            }
+            Ok(letter)
+        };
+        for letter in letters {
+            let letter = single_letter(letter.as_ref())?;
+            if alphabet.index_of(&letter).is_some() {
+                return Err(invalid(format!("'{}' appears twice in the alphabet", letter)));
+            }
+            alphabet.letters.push(letter);
+        }
+        for (variant, letter) in variants {
+            let variant = single_letter(variant.as_ref())?;
+            let index = alphabet
+                .index_of(&normalize(letter.as_ref()))
+                .ok_or_else(|| invalid(format!("'{}' is not in the alphabet", letter.as_ref())))?;
+            if alphabet.index_of(&variant).is_some() {
+                return Err(invalid(format!("'{}' appears twice in the alphabet", variant)));
+            }
+            let at = alphabet.variants.partition_point(|(v, _)| *v < variant);
+            alphabet.variants.insert(at, (variant, index));
+        }
+        Ok(alphabet)
+    }
+
+    fn from_preset(letters: &str, variants: &[(&str, &str)]) -> Self {
+        let letters: Vec<&str> = letters.graphemes(true).collect();
+        Alphabet::with_variants(&letters, variants).expect("built-in alphabets are valid")
+    }
+
+    // a to z
+    pub fn english() -> Self {
+        Alphabet::from_preset(ENGLISH, &[])
+    }
+
+    // a to z with ñ after n; accents are ignored
+    pub fn spanish() -> Self {
+        Alphabet::from_preset(SPANISH, SPANISH_VARIANTS)
+    }
+
+    // a to z, then ä, ö, ü and ß
+    pub fn german() -> Self {
+        Alphabet::from_preset(GERMAN, &[])
+    }
+
+    // α to ω; accents are ignored and final ς is σ
+    pub fn greek() -> Self {
+        Alphabet::from_preset(GREEK, GREEK_VARIANTS)
+    }
+
+    pub fn named(name: &str) -> Option<Self> {
+        match name {
+            "english" => Some(Alphabet::english()),
+            "spanish" => Some(Alphabet::spanish()),
+            "german" => Some(Alphabet::german()),
+            "greek" => Some(Alphabet::greek()),
+            _ => None,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.letters.len()
+    }
+
+    pub fn letters(&self) -> &[String] {
+        &self.letters
+    }
+
+    // Index of a letter given in lowercase NFC, variants included
+    pub fn index_of(&self, letter: &str) -> Option<u8> {
+        if let Some(index) = self.letters.iter().position(|l| l == letter) {
+            return Some(index as u8);
+        }
+        self.variants
+            .binary_search_by(|(v, _)| v.as_str().cmp(letter))
+            .ok()
+            .map(|i| self.variants[i].1)
+    }
+
+    // The letter indices of a word, in any case and normalization form
+    pub fn split(&self, word: &str) -> Result<Vec<u8>, String> {
+        normalize(word)
+            .graphemes(true)
+            .map(|g| self.index_of(g).ok_or_else(|| format!("'{}' is not a letter", g)))
+            .collect()
+    }
+
+    // The word with the given letter indices, which must be in the alphabet
+    pub fn spell(&self, letters: &[u8]) -> String {
+        letters.iter().map(|&c| self.letters[c as usize].as_str()).collect()
+    }
+
+    // Digest of the letters in order and of the variants, which is what binds
+    // parameters to an alphabet: two alphabets with the same number of letters
+    // give the same circuit, but not the same game
+    pub fn id(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(b"zk_wordle alphabet");
+        for letter in self.letters.iter() {
+            hasher.update((letter.len() as u32).to_le_bytes());
+            hasher.update(letter.as_bytes());
+        }
+        for (variant, index) in self.variants.iter() {
+            hasher.update((variant.len() as u32).to_le_bytes());
+            hasher.update(variant.as_bytes());
+            hasher.update([*index]);
+        }
+        hasher.finalize().into()
+    }
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Alphabet::english()
+    }
+}
+
+// The letters in order, without variants
+impl fmt::Display for Alphabet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.letters.concat())
+    }
+}
+
+// Either the name of a built-in alphabet or the letters themselves, in order
+impl FromStr for Alphabet {
+    type Err = ZkWordleError;
+
+    fn from_str(s: &str) -> Result<Self, ZkWordleError> {
+        if let Some(alphabet) = Alphabet::named(s) {
+            return Ok(alphabet);
+        }
+        let letters: Vec<&str> = s.graphemes(true).filter(|g| !g.trim().is_empty()).collect();
+        Alphabet::new(&letters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letters_are_grapheme_clusters() {
+        let spanish = Alphabet::spanish();
+        assert_eq!(spanish.size(), 27);
+        // precomposed ñ, and n followed by a combining tilde
+        for word in ["niño", "nin\u{303}o", "NIÑO"] {
+            assert_eq!(spanish.split(word).unwrap(), vec![13, 8, 14, 15]);
+        }
+        assert_eq!(spanish.spell(&spanish.split("CAMIÓN").unwrap()), "camion");
+        assert!(Alphabet::english().split("niño").is_err());
+
+        let german = Alphabet::german();
+        assert_eq!(german.spell(&german.split("STRAßE").unwrap()), "straße");
+        assert_eq!(german.split("äöüß").unwrap(), vec![26, 27, 28, 29]);
+
+        let greek = Alphabet::greek();
+        assert_eq!(greek.size(), 24);
+        assert_eq!(greek.split("ΛΌΓΟΣ").unwrap(), greek.split("λογοσ").unwrap());
+        assert_eq!(greek.spell(&greek.split("λόγος").unwrap()), "λογοσ");
+    }
+
+    #[test]
+    fn parses_names_and_letter_lists() {
+        assert_eq!("german".parse::<Alphabet>().unwrap(), Alphabet::german());
+        assert_eq!(ENGLISH.parse::<Alphabet>().unwrap(), Alphabet::english());
+        assert_eq!("a b c".parse::<Alphabet>().unwrap().size(), 3);
+        assert!("a".parse::<Alphabet>().is_err());
+        assert!("abca".parse::<Alphabet>().is_err());
+
+        // same size, different games
+        let reversed: String = ENGLISH.chars().rev().collect();
+        assert_ne!(reversed.parse::<Alphabet>().unwrap().id(), Alphabet::english().id());
+        assert_ne!(Alphabet::spanish().id(), Alphabet::from_preset(SPANISH, &[]).id());
+    }
+}
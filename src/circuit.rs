@@ -0,0 +1,505 @@
+// The Wordle circuit, written once as a bellman::Circuit over any prime field.
+//
+// The hidden word, the salt and the hidden word's path in the answer tree are
+// private witness variables; the commitment, the root of the answer tree, the
+// guess letters and the green/yellow flags are public inputs, so the shape of
+// the circuit never depends on the game being played. Each backend
+// synthesizes this same description, groth16.rs directly and spartan.rs
+// through the R1CS recorder in r1cs.rs.
+
+use bellman::{Circuit, ConstraintSystem, LinearCombination, SynthesisError, Variable};
+use ff::PrimeField;
+
+use crate::config::GameConfig;
+use crate::commitment::pack_word;
+use crate::feedback::LetterFeedback;
+use crate::merkle::MerklePath;
+use crate::mimc;
+
+// A linear combination together with its value, when the prover knows it
+#[derive(Clone)]
+struct Expr<F: PrimeField> {
+    lc: LinearCombination<F>,
+    value: Option<F>,
+}
+
+impl<F: PrimeField> Expr<F> {
+    fn var(var: Variable, value: Option<F>) -> Self {
+        Expr {
+            lc: LinearCombination::zero() + var,
+            value,
+        }
+    }
+
+    fn constant<CS: ConstraintSystem<F>>(c: F) -> Self {
+        Expr {
+            lc: LinearCombination::zero() + (c, CS::one()),
+            value: Some(c),
+        }
+    }
+
+    fn add(&self, other: &Expr<F>) -> Self {
+        Expr {
+            lc: self.lc.clone() + &other.lc,
+            value: self.value.zip(other.value).map(|(a, b)| a + b),
+        }
+    }
+
+    fn sub(&self, other: &Expr<F>) -> Self {
+        Expr {
+            lc: self.lc.clone() - &other.lc,
+            value: self.value.zip(other.value).map(|(a, b)| a - b),
+        }
+    }
+
+    fn scale(&self, coeff: F) -> Self {
+        let lc = self
+            .lc
+            .as_ref()
+            .iter()
+            .fold(LinearCombination::zero(), |lc, &(var, c)| lc + (c * coeff, var));
+        Expr {
+            lc,
+            value: self.value.map(|v| v * coeff),
+        }
+    }
+}
+
+fn alloc<F: PrimeField, CS: ConstraintSystem<F>>(cs: &mut CS, name: &str, value: Option<F>) -> Result<Expr<F>, SynthesisError> {
+    let var = cs.alloc(|| name, || value.ok_or(SynthesisError::AssignmentMissing))?;
+    Ok(Expr::var(var, value))
+}
+
+fn alloc_input<F: PrimeField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    name: &str,
+    value: Option<F>,
+) -> Result<Expr<F>, SynthesisError> {
+    let var = cs.alloc_input(|| name, || value.ok_or(SynthesisError::AssignmentMissing))?;
+    Ok(Expr::var(var, value))
+}
+
+fn enforce<F: PrimeField, CS: ConstraintSystem<F>>(cs: &mut CS, name: &str, a: &Expr<F>, b: &Expr<F>, c: &Expr<F>) {
+    cs.enforce(|| name, |_| a.lc.clone(), |_| b.lc.clone(), |_| c.lc.clone());
+}
+
+// Allocates a * b and constrains it
+fn mul<F: PrimeField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    name: &str,
+    a: &Expr<F>,
+    b: &Expr<F>,
+) -> Result<Expr<F>, SynthesisError> {
+    let mut cs = cs.namespace(|| name);
+    let product = alloc(&mut cs, "value", a.value.zip(b.value).map(|(a, b)| a * b))?;
+    enforce(&mut cs, "product", a, b, &product);
+    Ok(product)
+}
+
+// out = 1 if d == 0 else 0, with the prover supplying the inverse of d
+fn is_zero<F: PrimeField, CS: ConstraintSystem<F>>(cs: &mut CS, d: &Expr<F>, out: &Expr<F>) -> Result<(), SynthesisError> {
+    let one = Expr::constant::<CS>(F::ONE);
+    let zero = Expr::constant::<CS>(F::ZERO);
+    let inv = alloc(cs, "inv", d.value.map(|d| d.invert().unwrap_or(F::ZERO)))?;
+    enforce(cs, "d * inv = 1 - out", d, &inv, &one.sub(out));
+    enforce(cs, "d * out = 0", d, out, &zero);
+    Ok(())
+}
+
+// x(x - 1)...(x - (n - 1)) = 0, i.e. x lies in 0..n
+fn range_check<F: PrimeField, CS: ConstraintSystem<F>>(cs: &mut CS, x: &Expr<F>, n: usize) -> Result<(), SynthesisError> {
+    let shifted = |k: usize| x.sub(&Expr::constant::<CS>(F::from(k as u64)));
+    let mut p = x.clone();
+    for k in 1..n - 1 {
+        p = mul(cs, &format!("factor {}", k), &p, &shifted(k))?;
+    }
+    enforce(cs, "product is zero", &p, &shifted(n - 1), &Expr::constant::<CS>(F::ZERO));
+    Ok(())
+}
+
+// MiMC encryption E_key(x0), three multiplications per round
+fn mimc_encrypt<F: PrimeField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    key: &Expr<F>,
+    x0: &Expr<F>,
+    constants: &[F],
+) -> Result<Expr<F>, SynthesisError> {
+    let mut x = x0.clone();
+    for (r, c) in constants.iter().enumerate() {
+        let mut cs = cs.namespace(|| format!("round {}", r));
+        let t = x.add(key).add(&Expr::constant::<CS>(*c));
+        let sq = mul(&mut cs, "sq", &t, &t)?;
+        let quad = mul(&mut cs, "quad", &sq, &sq)?;
+        x = mul(&mut cs, "out", &quad, &t)?;
+    }
+    Ok(x.add(key))
+}
+
+// Copies of guess[i] still available for a yellow, and earlier non-green copies of
+// guess[i] in the guess
+fn yellow_counts(hidden_word: &[u8], guess: &[u8], i: usize) -> (i64, i64) {
+    let available = (0..hidden_word.len())
+        .filter(|&j| j != i && hidden_word[j] == guess[i] && hidden_word[j] != guess[j])
+        .count();
+    let prior = (0..i)
+        .filter(|&k| guess[k] == guess[i] && guess[k] != hidden_word[k])
+        .count();
+    (available as i64, prior as i64)
+}
+
+// One Wordle turn. Values are None during parameter generation; the config
+// alone fixes the shape. Values that do not fit that shape fail synthesis.
+pub struct WordleCircuit<F: PrimeField> {
+    config: GameConfig,
+    hidden_word: Option<Vec<u8>>,
+    salt: Option<F>,
+    commitment: Option<F>,
+    answers_root: Option<F>,
+    answer_path: Option<MerklePath<F>>,
+    guess: Option<Vec<u8>>,
+    feedback: Option<Vec<LetterFeedback>>,
+}
+
+impl<F: PrimeField> WordleCircuit<F> {
+    pub fn blank(config: &GameConfig) -> Self {
+        WordleCircuit {
+            config: config.clone(),
+            hidden_word: None,
+            salt: None,
+            commitment: None,
+            answers_root: None,
+            answer_path: None,
+            guess: None,
+            feedback: None,
+        }
+    }
+
+    pub(crate) fn for_turn(
+        config: &GameConfig,
+        hidden_word: &[u8],
+        salt: F,
+        commitment: F,
+        answer_path: &MerklePath<F>,
+        guess: &[u8],
+        feedback: &[LetterFeedback],
+    ) -> Self {
+        WordleCircuit {
+            config: config.clone(),
+            hidden_word: Some(hidden_word.to_vec()),
+            salt: Some(salt),
+            commitment: Some(commitment),
+            answers_root: Some(answer_path.root(pack_word(config, hidden_word))),
+            answer_path: Some(answer_path.clone()),
+            guess: Some(guess.to_vec()),
+            feedback: Some(feedback.to_vec()),
+        }
+    }
+
+    // Words and feedback of word_len letters and a path as deep as the answer
+    // tree, wherever they are known; synthesize indexes them by both
+    fn fits_shape(&self) -> bool {
+        let fits = |len: Option<usize>, want: usize| len.is_none_or(|len| len == want);
+        fits(self.hidden_word.as_ref().map(Vec::len), self.config.word_len)
+            && fits(self.guess.as_ref().map(Vec::len), self.config.word_len)
+            && fits(self.feedback.as_ref().map(Vec::len), self.config.word_len)
+            && fits(self.answer_path.as_ref().map(MerklePath::depth), self.config.answer_tree_depth)
+    }
+}
+
+impl<F: PrimeField> Circuit<F> for WordleCircuit<F> {
+    fn synthesize<CS: ConstraintSystem<F>>(self, cs: &mut CS) -> Result<(), SynthesisError> {
+        if !self.fits_shape() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let letter = |word: &Option<Vec<u8>>, i: usize| word.as_ref().map(|w| F::from(w[i] as u64));
+        let flag = |i: usize, want: LetterFeedback| self.feedback.as_ref().map(|f| F::from((f[i] == want) as u64));
+        let both = self.hidden_word.as_ref().zip(self.guess.as_ref());
+        let one = Expr::constant::<CS>(F::ONE);
+        let word_len = self.config.word_len;
+        let alphabet_size = self.config.alphabet.size();
+
+        // Public inputs, in the order of public_inputs()
+        let commitment = alloc_input(cs, "commitment", self.commitment)?;
+        let answers_root = alloc_input(cs, "answers root", self.answers_root)?;
+        let mut guess = Vec::new();
+        let mut green = Vec::new();
+        let mut yellow = Vec::new();
+        for i in 0..word_len {
+            guess.push(alloc_input(cs, &format!("guess {}", i), letter(&self.guess, i))?);
+        }
+        for i in 0..word_len {
+            green.push(alloc_input(cs, &format!("green {}", i), flag(i, LetterFeedback::Green))?);
+        }
+        for i in 0..word_len {
+            yellow.push(alloc_input(cs, &format!("yellow {}", i), flag(i, LetterFeedback::Yellow))?);
+        }
+
+        // Witness: hidden word and salt
+        let mut hidden = Vec::new();
+        for i in 0..word_len {
+            hidden.push(alloc(cs, &format!("hidden {}", i), letter(&self.hidden_word, i))?);
+        }
+        let salt = alloc(cs, "salt", self.salt)?;
+
+        // commitment = H(salt, pack_word(hidden_word))
+        let constants = mimc::round_constants::<F>();
+        let zero = Expr::constant::<CS>(F::ZERO);
+        let h1 = mimc_encrypt(&mut cs.namespace(|| "mimc salt"), &zero, &salt, &constants)?.add(&salt);
+        let mut packed = zero.clone();
+        let mut place = F::ONE;
+        for h in hidden.iter() {
+            packed = packed.add(&h.scale(place));
+            place *= F::from(alphabet_size as u64);
+        }
+        let h2 = mimc_encrypt(&mut cs.namespace(|| "mimc word"), &h1, &packed, &constants)?
+            .add(&h1)
+            .add(&packed);
+        enforce(cs, "commitment opens", &h2, &one, &commitment);
+
+        // the packed word is a leaf of the answer tree. At each level the
+        // prover supplies the sibling and which side it is on; with
+        // swap = is_right * (sibling - node) the children are
+        // (node + swap, sibling - swap). Each parent gets a variable of its
+        // own, as its expression repeats the children's.
+        let path = self.answer_path.as_ref();
+        let mut node = packed;
+        for level in 0..self.config.answer_tree_depth {
+            let mut cs = cs.namespace(|| format!("answer level {}", level));
+            let is_right = alloc(&mut cs, "is right", path.map(|p| F::from(p.is_right(level) as u64)))?;
+            enforce(&mut cs, "is right is boolean", &is_right, &one.sub(&is_right), &zero);
+            let sibling = alloc(&mut cs, "sibling", path.map(|p| p.siblings()[level]))?;
+            let swap = mul(&mut cs, "swap", &is_right, &sibling.sub(&node))?;
+            let (left, right) = (node.add(&swap), sibling.sub(&swap));
+            let parent = mimc_encrypt(&mut cs.namespace(|| "mimc"), &left, &right, &constants)?
+                .add(&left)
+                .add(&right);
+            node = alloc(&mut cs, "parent", parent.value)?;
+            enforce(&mut cs, "parent hash", &parent, &one, &node);
+        }
+        enforce(cs, "word is an answer", &node, &one, &answers_root);
+
+        // every hidden letter lies in 0..alphabet_size
+        for (i, h) in hidden.iter().enumerate() {
+            range_check(&mut cs.namespace(|| format!("range {}", i)), h, alphabet_size)?;
+        }
+
+        // eq[i][j] = (guess[i] == hidden[j]); the diagonal is the public green flag
+        let mut eq = vec![Vec::new(); word_len];
+        for i in 0..word_len {
+            for j in 0..word_len {
+                let mut cs = cs.namespace(|| format!("eq {} {}", i, j));
+                let out = if i == j {
+                    green[i].clone()
+                } else {
+                    let value = both.map(|(h, g)| F::from((g[i] == h[j]) as u64));
+                    alloc(&mut cs, "out", value)?
+                };
+                is_zero(&mut cs, &guess[i].sub(&hidden[j]), &out)?;
+                eq[i].push(out);
+            }
+        }
+
+        for i in 0..word_len {
+            let mut cs = cs.namespace(|| format!("position {}", i));
+
+            // copies of guess[i] in the hidden word that are not already green
+            let mut available = zero.clone();
+            for j in (0..word_len).filter(|&j| j != i) {
+                let unmatched = mul(&mut cs, &format!("unmatched {}", j), &eq[i][j], &one.sub(&green[j]))?;
+                available = available.add(&unmatched);
+            }
+
+            // earlier non-green copies of the same letter, which claim the yellows first
+            let mut prior = zero.clone();
+            for k in 0..i {
+                let mut cs = cs.namespace(|| format!("same {}", k));
+                let value = self.guess.as_ref().map(|g| F::from((g[i] == g[k]) as u64));
+                let same = alloc(&mut cs, "out", value)?;
+                is_zero(&mut cs, &guess[i].sub(&guess[k]), &same)?;
+                let earlier = mul(&mut cs, "earlier", &same, &one.sub(&green[k]))?;
+                prior = prior.add(&earlier);
+            }
+
+            // ahead = (available - prior >= 1). With t = available - prior, the
+            // prover picks the boolean ahead and we check that
+            // r = ahead * (2t - 1) - t lies in 0..word_len, which holds only for
+            // t - 1 >= 0 when ahead = 1 and for -t >= 0 when ahead = 0.
+            let ahead_value = both.map(|(h, g)| {
+                let (available, prior) = yellow_counts(h, g, i);
+                F::from((available - prior >= 1) as u64)
+            });
+            let ahead = alloc(&mut cs, "ahead", ahead_value)?;
+            enforce(&mut cs, "ahead is boolean", &ahead, &one.sub(&ahead), &zero);
+            let t = available.sub(&prior);
+            let scaled = mul(&mut cs, "scaled", &ahead, &t.scale(F::from(2)).sub(&one))?;
+            range_check(&mut cs.namespace(|| "ahead range"), &scaled.sub(&t), word_len)?;
+
+            // yellow[i] = (1 - green[i]) * ahead[i]
+            enforce(&mut cs, "yellow", &one.sub(&green[i]), &ahead, &yellow[i]);
+        }
+
+        Ok(())
+    }
+}
+
+// Public inputs in the order WordleCircuit allocates them
+pub(crate) fn public_inputs<F: PrimeField>(
+    commitment: F,
+    answers_root: F,
+    guess: &[u8],
+    feedback: &[LetterFeedback],
+) -> Vec<F> {
+    let mut inputs = vec![commitment, answers_root];
+    inputs.extend(guess.iter().map(|&g| F::from(g as u64)));
+    let flag = |f: &LetterFeedback, want: LetterFeedback| F::from((*f == want) as u64);
+    inputs.extend(feedback.iter().map(|f| flag(f, LetterFeedback::Green)));
+    inputs.extend(feedback.iter().map(|f| flag(f, LetterFeedback::Yellow)));
+    inputs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::Alphabet;
+    use crate::commitment::Commitment;
+    use crate::feedback::tests::{reference_score, word_pair, TRICKY_PAIRS};
+    use crate::feedback::Feedback;
+    use crate::merkle::WordTree;
+    use bellman::gadgets::test::TestConstraintSystem;
+    use curve25519_dalek::scalar::Scalar;
+    use proptest::prelude::*;
+    use LetterFeedback::{Gray, Green, Yellow};
+
+    fn synthesize(
+        config: &GameConfig,
+        hidden_word: &[u8],
+        guess: &[u8],
+        feedback: &[LetterFeedback],
+    ) -> TestConstraintSystem<Scalar> {
+        let salt = Scalar::from(7u8);
+        let commitment: Scalar = Commitment::new(config, hidden_word, &salt).field_element().unwrap();
+        let answers = WordTree::new(config, config.answer_tree_depth, &[hidden_word.to_vec()]).unwrap();
+        let path = answers.path(hidden_word).unwrap();
+        let circuit = WordleCircuit::for_turn(config, hidden_word, salt, commitment, &path, guess, feedback);
+
+        let mut cs = TestConstraintSystem::<Scalar>::new();
+        circuit.synthesize(&mut cs).unwrap();
+        cs
+    }
+
+    fn is_satisfied(config: &GameConfig, hidden_word: &str, guess: &str, feedback: &[LetterFeedback]) -> bool {
+        let split = |word: &str| config.alphabet.split(word).unwrap();
+        synthesize(config, &split(hidden_word), &split(guess), feedback).is_satisfied()
+    }
+
+    fn accepts_only(config: &GameConfig, hidden_word: &str, guess: &str, expected: &[LetterFeedback]) {
+        assert!(is_satisfied(config, hidden_word, guess, expected), "{} against {}", guess, hidden_word);
+
+        for i in 0..expected.len() {
+            for wrong in [Gray, Yellow, Green].into_iter().filter(|&f| f != expected[i]) {
+                let mut feedback = expected.to_vec();
+                feedback[i] = wrong;
+                assert!(!is_satisfied(config, hidden_word, guess, &feedback), "{} against {}", guess, hidden_word);
+            }
+        }
+    }
+
+    #[test]
+    fn circuit_accepts_only_the_scored_feedback() {
+        for (hidden_word, guess, expected) in TRICKY_PAIRS {
+            accepts_only(&GameConfig::default(), hidden_word, guess, expected);
+        }
+    }
+
+    #[test]
+    fn circuit_fits_every_word_length_and_alphabet() {
+        for (word_len, alphabet, hidden_word, guess) in [
+            (4, Alphabet::english(), "tree", "rote"),
+            (7, Alphabet::english(), "cabbage", "baggage"),
+            (5, "abc".parse().unwrap(), "abaca", "cabbb"),
+            (5, Alphabet::german(), "größe", "grüße"),
+            (5, Alphabet::greek(), "λόγος", "σοφία"),
+        ] {
+            let config = GameConfig::new(word_len, alphabet, Some(6)).unwrap();
+            let hidden_letters = config.alphabet.split(hidden_word).unwrap();
+            let guess_letters = config.alphabet.split(guess).unwrap();
+            let scored = Feedback::score(&hidden_letters, &guess_letters);
+            accepts_only(&config, hidden_word, guess, scored.letters());
+
+            let cs = synthesize(&config, &hidden_letters, &guess_letters, scored.letters());
+            assert_eq!(cs.num_inputs(), 3 + 3 * word_len);
+        }
+    }
+
+    #[test]
+    fn hidden_word_must_be_an_answer() {
+        let config = GameConfig::default();
+        let split = |word: &str| config.alphabet.split(word).unwrap();
+        let answers =
+            WordTree::<Scalar>::new(&config, config.answer_tree_depth, &[split("crane"), split("slate")]).unwrap();
+        let salt = Scalar::from(7u8);
+        let guess = split("crane");
+        let path = answers.path(&split("slate")).unwrap();
+        let circuit_for = |hidden_word: &[u8]| {
+            let commitment = Commitment::new(&config, hidden_word, &salt).field_element().unwrap();
+            let feedback = Feedback::score(hidden_word, &guess);
+            let mut circuit =
+                WordleCircuit::for_turn(&config, hidden_word, salt, commitment, &path, &guess, feedback.letters());
+            circuit.answers_root = Some(answers.root().field_element().unwrap());
+            circuit
+        };
+
+        let mut cs = TestConstraintSystem::<Scalar>::new();
+        circuit_for(&split("slate")).synthesize(&mut cs).unwrap();
+        assert!(cs.is_satisfied());
+
+        // no path leads from a word outside the list to the root
+        let mut cs = TestConstraintSystem::<Scalar>::new();
+        circuit_for(&split("zzzzz")).synthesize(&mut cs).unwrap();
+        assert_eq!(cs.which_is_unsatisfied(), Some("word is an answer"));
+    }
+
+    #[test]
+    fn values_must_fit_the_config() {
+        let config = GameConfig::default();
+        let split = |word: &str| config.alphabet.split(word).unwrap();
+        let answers = WordTree::<Scalar>::new(&config, config.answer_tree_depth, &[split("crane")]).unwrap();
+        let shallow = WordTree::<Scalar>::new(&config, 2, &[split("crane")]).unwrap();
+        let salt = Scalar::from(7u8);
+        let commitment = Commitment::new(&config, &split("crane"), &salt).field_element().unwrap();
+        let feedback = Feedback::score(&split("crane"), &split("slate"));
+        for (path, guess, feedback) in [
+            (shallow.path(&split("crane")).unwrap(), split("slate"), feedback.letters()),
+            (answers.path(&split("crane")).unwrap(), split("slat"), feedback.letters()),
+            (answers.path(&split("crane")).unwrap(), split("slate"), &feedback.letters()[1..]),
+        ] {
+            let circuit = WordleCircuit::for_turn(&config, &split("crane"), salt, commitment, &path, &guess, feedback);
+            let mut cs = TestConstraintSystem::<Scalar>::new();
+            assert!(matches!(circuit.synthesize(&mut cs), Err(SynthesisError::Unsatisfiable)));
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        // The feedback the prover claims, the feedback the circuit's own witness
+        // implies (green from the letters, yellow from the ahead flags) and the
+        // reference scorer all agree, and the circuit accepts it
+        #[test]
+        fn circuit_agrees_with_the_reference((hidden_word, guess) in word_pair()) {
+            let scored = Feedback::score(&hidden_word, &guess);
+            let mut cs = synthesize(&GameConfig::default(), &hidden_word, &guess, scored.letters());
+            prop_assert!(cs.is_satisfied(), "{:?}", cs.which_is_unsatisfied());
+
+            let implied: Vec<LetterFeedback> = (0..hidden_word.len())
+                .map(|i| match (guess[i] == hidden_word[i], cs.get(&format!("position {}/ahead", i)) == Scalar::ONE) {
+                    (true, _) => Green,
+                    (false, true) => Yellow,
+                    (false, false) => Gray,
+                })
+                .collect();
+            prop_assert_eq!(&implied[..], scored.letters());
+            prop_assert_eq!(&reference_score(&hidden_word, &guess)[..], scored.letters());
+        }
+    }
+}
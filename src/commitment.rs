@@ -0,0 +1,198 @@
+use ff::PrimeField;
+use rand::{thread_rng, RngCore};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::config::GameConfig;
+use crate::error::ZkWordleError;
+use crate::guess::check_letters;
+use crate::mimc;
+
+// Salted commitment H(salt, word) published before the first guess, as the
+// canonical little-endian encoding of a field element of the proof backend
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Commitment([u8; 32]);
+
+impl Commitment {
+    pub(crate) fn new<F: PrimeField<Repr = [u8; 32]>>(config: &GameConfig, hidden_word: &[u8], salt: &F) -> Self {
+        Commitment(mimc::hash(&[*salt, pack_word::<F>(config, hidden_word)]).to_repr())
+    }
+
+    // Unchecked: each backend verifies the bytes encode an element of its field
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Commitment(bytes)
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+
+    // The verifier feeds these bytes straight into the circuit as a field element
+    pub(crate) fn field_element<F: PrimeField<Repr = [u8; 32]>>(&self) -> Result<F, ZkWordleError> {
+        Option::from(F::from_repr(self.0)).ok_or(ZkWordleError::InvalidCommitment)
+    }
+}
+
+impl fmt::Display for Commitment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_hex(f, &self.0)
+    }
+}
+
+impl FromStr for Commitment {
+    type Err = ZkWordleError;
+
+    fn from_str(s: &str) -> Result<Self, ZkWordleError> {
+        parse_hex(s.trim()).map(Commitment).ok_or(ZkWordleError::InvalidCommitment)
+    }
+}
+
+// Opening of a commitment: the hidden word and its salt. The host keeps it
+// between turns and discloses it once the game is over. Written out as the word
+// followed by the salt in hex; the word is spelled in the game's alphabet and
+// only checked against the game when the secret is used.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameSecret {
+    word: String,
+    salt: [u8; 32],
+}
+
+impl GameSecret {
+    // Draws a fresh salt, uniform in F, for the word
+    pub fn generate<F: PrimeField<Repr = [u8; 32]>>(
+        config: &GameConfig,
+        hidden_word: Vec<u8>,
+    ) -> Result<Self, ZkWordleError> {
+        check_letters(config, &hidden_word).map_err(ZkWordleError::InvalidWord)?;
+
+        let mut salt_bytes = [0u8; 64];
+        thread_rng().fill_bytes(&mut salt_bytes);
+        let salt: F = mimc::from_uniform_bytes(&salt_bytes);
+        Ok(GameSecret::new(config.alphabet.spell(&hidden_word), &salt))
+    }
+
+    pub(crate) fn new<F: PrimeField<Repr = [u8; 32]>>(word: String, salt: &F) -> Self {
+        GameSecret {
+            word,
+            salt: salt.to_repr(),
+        }
+    }
+
+    // The word as letter indices, if it is one of this game's words
+    pub fn hidden_word(&self, config: &GameConfig) -> Result<Vec<u8>, ZkWordleError> {
+        let hidden_word = config.alphabet.split(&self.word).map_err(ZkWordleError::InvalidSecret)?;
+        check_letters(config, &hidden_word).map_err(ZkWordleError::InvalidSecret)?;
+        Ok(hidden_word)
+    }
+
+    pub fn word(&self) -> &str {
+        &self.word
+    }
+
+    pub(crate) fn salt<F: PrimeField<Repr = [u8; 32]>>(&self) -> Result<F, ZkWordleError> {
+        Option::from(F::from_repr(self.salt))
+            .ok_or_else(|| ZkWordleError::InvalidSecret("salt is not a canonical field element".to_string()))
+    }
+
+    // Fails if the word is not one of this game's words
+    pub fn commitment<F: PrimeField<Repr = [u8; 32]>>(&self, config: &GameConfig) -> Result<Commitment, ZkWordleError> {
+        Ok(Commitment::new(config, &self.hidden_word(config)?, &self.salt::<F>()?))
+    }
+
+    // Whether this is the opening of commitment, with the salt taken in F
+    pub fn opens<F: PrimeField<Repr = [u8; 32]>>(&self, config: &GameConfig, commitment: &Commitment) -> bool {
+        self.commitment::<F>(config).is_ok_and(|c| c == *commitment)
+    }
+}
+
+impl fmt::Display for GameSecret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ", self.word)?;
+        write_hex(f, &self.salt)
+    }
+}
+
+impl FromStr for GameSecret {
+    type Err = ZkWordleError;
+
+    fn from_str(s: &str) -> Result<Self, ZkWordleError> {
+        let invalid = |reason: &str| ZkWordleError::InvalidSecret(reason.to_string());
+        let mut parts = s.split_whitespace();
+        let (Some(word), Some(salt), None) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(invalid("expected the word and the salt"));
+        };
+        let salt = parse_hex(salt).ok_or_else(|| invalid("the salt must be 32 bytes of hex"))?;
+        Ok(GameSecret {
+            word: word.to_string(),
+            salt,
+        })
+    }
+}
+
+pub(crate) fn write_hex(f: &mut fmt::Formatter, bytes: &[u8]) -> fmt::Result {
+    for b in bytes.iter() {
+        write!(f, "{:02x}", b)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn parse_hex(s: &str) -> Option<[u8; 32]> {
+    if s.len() != 64 || !s.bytes().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let mut bytes = [0u8; 32];
+    for (b, pair) in bytes.iter_mut().zip(s.as_bytes().chunks(2)) {
+        *b = u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok()?;
+    }
+    Some(bytes)
+}
+
+// Packs the word into a single field element, little-endian in base alphabet size
+pub(crate) fn pack_word<F: PrimeField>(config: &GameConfig, word: &[u8]) -> F {
+    word.iter()
+        .rev()
+        .fold(F::ZERO, |acc, &c| acc * F::from(config.alphabet.size() as u64) + F::from(c as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::Alphabet;
+    use crate::feedback::tests::letters;
+    use curve25519_dalek::scalar::Scalar;
+
+    #[test]
+    fn secrets_and_commitments_round_trip_through_text() {
+        let config = GameConfig::default();
+        let secret = GameSecret::generate::<Scalar>(&config, letters("crane")).unwrap();
+        let commitment = secret.commitment::<Scalar>(&config).unwrap();
+
+        let parsed: GameSecret = secret.to_string().parse().unwrap();
+        assert_eq!(parsed, secret);
+        assert_eq!(parsed.word(), "crane");
+        assert_eq!(commitment.to_string().parse::<Commitment>().unwrap(), commitment);
+        assert!(parsed.opens::<Scalar>(&config, &commitment));
+
+        let other = GameSecret::new("crane".to_string(), &Scalar::from(7u64));
+        assert!(!other.opens::<Scalar>(&config, &commitment));
+        // same word and salt, but a game of another shape
+        let short = GameConfig::new(5, "abcdefghijklmnopqrst".parse().unwrap(), Some(6)).unwrap();
+        assert!(!parsed.opens::<Scalar>(&short, &commitment));
+
+        for bad in ["crane", "crane 00", &format!("crane {} extra", "0".repeat(64))] {
+            assert!(matches!(bad.parse::<GameSecret>(), Err(ZkWordleError::InvalidSecret(_))), "{:?}", bad);
+        }
+        // words are checked against the game once it is known
+        for word in ["cr4ne", "cranes"] {
+            let secret: GameSecret = format!("{} {}", word, "0".repeat(64)).parse().unwrap();
+            assert!(matches!(secret.commitment::<Scalar>(&config), Err(ZkWordleError::InvalidSecret(_))));
+        }
+
+        let greek = GameConfig::new(5, Alphabet::greek(), Some(6)).unwrap();
+        let secret = GameSecret::generate::<Scalar>(&greek, greek.alphabet.split("λόγος").unwrap()).unwrap();
+        let parsed: GameSecret = secret.to_string().parse().unwrap();
+        assert_eq!(parsed.word(), "λογοσ");
+        assert!(parsed.opens::<Scalar>(&greek, &secret.commitment::<Scalar>(&greek).unwrap()));
+        assert!("+f".repeat(32).parse::<Commitment>().is_err());
+    }
+}
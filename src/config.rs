@@ -0,0 +1,102 @@
+use std::fmt;
+
+use crate::alphabet::Alphabet;
+use crate::error::ZkWordleError;
+
+// Word length of the classic game, the default
+pub const DEFAULT_WORD_LEN: usize = 5;
+
+// Longest supported word. The circuit grows with the square of the length, and
+// a packed word has to stay well below the size of either proof field.
+pub const MAX_WORD_LEN: usize = 16;
+
+// The answer list is a Merkle tree of this depth unless the config says
+// otherwise: up to 4096 answers, enough for the official list. Every level
+// costs a MiMC compression in the circuit.
+pub const DEFAULT_ANSWER_TREE_DEPTH: usize = 12;
+pub const MAX_ANSWER_TREE_DEPTH: usize = 20;
+
+// Rules of one variant of the game. The word length and the size of the
+// alphabet fix the shape of the circuit, and parameters are also bound to the
+// alphabet itself, so every variant needs its own parameters; so does the
+// depth of the tree the answers are hashed into. The number of guesses is only
+// enforced by whoever runs the game.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GameConfig {
+    pub word_len: usize,
+    pub alphabet: Alphabet,
+    // None for practice games, which go on until the word is found
+    pub max_guesses: Option<usize>,
+    // the answer list holds up to 2^answer_tree_depth words
+    pub answer_tree_depth: usize,
+}
+
+impl GameConfig {
+    pub fn new(word_len: usize, alphabet: Alphabet, max_guesses: Option<usize>) -> Result<Self, ZkWordleError> {
+        let config = GameConfig {
+            word_len,
+            alphabet,
+            max_guesses,
+            answer_tree_depth: DEFAULT_ANSWER_TREE_DEPTH,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<(), ZkWordleError> {
+        if !(2..=MAX_WORD_LEN).contains(&self.word_len) {
+            return Err(ZkWordleError::InvalidConfig(format!(
+                "words must have 2 to {} letters, not {}",
+                MAX_WORD_LEN, self.word_len
+            )));
+        }
+        if !(1..=MAX_ANSWER_TREE_DEPTH).contains(&self.answer_tree_depth) {
+            return Err(ZkWordleError::InvalidConfig(format!(
+                "the answer tree must have a depth of 1 to {}, not {}",
+                MAX_ANSWER_TREE_DEPTH, self.answer_tree_depth
+            )));
+        }
+        if self.max_guesses == Some(0) {
+            return Err(ZkWordleError::InvalidConfig("a game needs at least one guess".to_string()));
+        }
+        Ok(())
+    }
+
+    // Name of the variant, as used in parameter file names: word length x
+    // alphabet size, as in "5x26", followed for any alphabet but English by the
+    // start of its id, as in "5x27-0c1d2e3f", and for any answer tree depth but
+    // the default by the depth, as in "5x26-d10"
+    pub fn shape_name(&self) -> String {
+        let mut shape = format!("{}x{}", self.word_len, self.alphabet.size());
+        if self.alphabet != Alphabet::english() {
+            let id = self.alphabet.id();
+            shape += &format!("-{:02x}{:02x}{:02x}{:02x}", id[0], id[1], id[2], id[3]);
+        }
+        if self.answer_tree_depth != DEFAULT_ANSWER_TREE_DEPTH {
+            shape += &format!("-d{}", self.answer_tree_depth);
+        }
+        shape
+    }
+}
+
+// The classic game: five English letters, six guesses
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig {
+            word_len: DEFAULT_WORD_LEN,
+            alphabet: Alphabet::english(),
+            max_guesses: Some(6),
+            answer_tree_depth: DEFAULT_ANSWER_TREE_DEPTH,
+        }
+    }
+}
+
+impl fmt::Display for GameConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-letter words over {}, ", self.word_len, self.alphabet)?;
+        match self.max_guesses {
+            Some(max_guesses) => write!(f, "{} guesses", max_guesses),
+            None => write!(f, "unlimited guesses"),
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use ff::PrimeField;
+use flate2::read::GzDecoder;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+use crate::alphabet::Alphabet;
+use crate::config::GameConfig;
+use crate::error::ZkWordleError;
+use crate::merkle::WordTree;
+
+// Built into the binary so a game needs no word list on disk. Both are English;
+// the answers come in every length from 4 to 7 letters, the extra guesses only
+// in 5.
+const EMBEDDED_ANSWERS: &str = include_str!("../words/answers.txt");
+const EMBEDDED_ALLOWED: &str = include_str!("../words/allowed.txt");
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+// Word lists of a game, split as in the official Wordle: the hidden word is
+// drawn from the answers, while a guess may be any allowed word. Both lists are
+// lowercase, sorted and free of duplicates, and every answer is also allowed.
+// Only words of the game's length and alphabet are kept, spelled with the
+// alphabet's own letters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dictionary {
+    config: GameConfig,
+    answers: Vec<String>,
+    allowed: Vec<String>,
+}
+
+impl Dictionary {
+    pub fn new(config: &GameConfig, answers: Vec<String>, extra_guesses: Vec<String>) -> Result<Self, ZkWordleError> {
+        let answers = normalize_words(config, answers);
+        if answers.is_empty() {
+            return Err(no_words(config));
+        }
+        let allowed = normalize_words(config, answers.iter().cloned().chain(extra_guesses).collect());
+        Ok(Dictionary {
+            config: config.clone(),
+            answers,
+            allowed,
+        })
+    }
+
+    // Fails for games the built-in lists have no words for, which includes
+    // every alphabet but English
+    pub fn embedded(config: &GameConfig) -> Result<Self, ZkWordleError> {
+        if config.alphabet != Alphabet::english() {
+            return Err(no_words(config));
+        }
+        let lines = |list: &str| list.lines().map(str::to_string).collect();
+        Dictionary::new(config, lines(EMBEDDED_ANSWERS), lines(EMBEDDED_ALLOWED))
+    }
+
+    // Answers from one file, optionally more allowed guesses from another
+    pub fn load<P: AsRef<Path>, Q: AsRef<Path>>(
+        config: &GameConfig,
+        answers: P,
+        extra_guesses: Option<Q>,
+    ) -> Result<Self, ZkWordleError> {
+        let extra_guesses = match extra_guesses {
+            Some(path) => load_words(config, path)?,
+            None => Vec::new(),
+        };
+        Dictionary::new(config, load_words(config, answers)?, extra_guesses)
+    }
+
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    pub fn answers(&self) -> &[String] {
+        &self.answers
+    }
+
+    pub fn allowed(&self) -> &[String] {
+        &self.allowed
+    }
+
+    pub fn is_allowed(&self, word: &str) -> bool {
+        self.allowed.binary_search_by(|w| w.as_str().cmp(word)).is_ok()
+    }
+
+    // The answers as a tree of the config's depth, whose root players check
+    // the hidden word against. Fails if there are more answers than leaves.
+    pub fn answer_tree<F: PrimeField<Repr = [u8; 32]>>(&self) -> Result<WordTree<F>, ZkWordleError> {
+        WordTree::new(&self.config, self.config.answer_tree_depth, &self.letters(&self.answers)?)
+    }
+
+    // The allowed guesses as a tree just deep enough to hold them, whose root
+    // players check rejected guesses against. Only ever checked outside the
+    // circuit, so its depth is not part of the config.
+    pub fn allowed_tree<F: PrimeField<Repr = [u8; 32]>>(&self) -> Result<WordTree<F>, ZkWordleError> {
+        let depth = self.allowed.len().next_power_of_two().trailing_zeros() as usize;
+        WordTree::new(&self.config, depth, &self.letters(&self.allowed)?)
+    }
+
+    fn letters(&self, words: &[String]) -> Result<Vec<Vec<u8>>, ZkWordleError> {
+        words
+            .iter()
+            .map(|word| self.config.alphabet.split(word).map_err(ZkWordleError::InvalidWord))
+            .collect()
+    }
+}
+
+// Reads a word list as plain text (one word per line) or as a JSON array of
+// strings, either of them optionally gzipped; the format is told from the
+// contents, not the file name. Keeps the words of the game.
+pub fn load_words<P: AsRef<Path>>(config: &GameConfig, path: P) -> Result<Vec<String>, ZkWordleError> {
+    let words = normalize_words(config, parse_words(&fs::read(path)?)?);
+    if words.is_empty() {
+        return Err(no_words(config));
+    }
+    Ok(words)
+}
+
+fn parse_words(bytes: &[u8]) -> Result<Vec<String>, ZkWordleError> {
+    if bytes.starts_with(&GZIP_MAGIC) {
+        let mut decompressed = Vec::new();
+        GzDecoder::new(bytes).read_to_end(&mut decompressed)?;
+        return parse_words(&decompressed);
+    }
+
+    let text = std::str::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    if text.trim_start().starts_with('[') {
+        Ok(serde_json::from_str(text).map_err(io::Error::from)?)
+    } else {
+        Ok(text.lines().map(str::to_string).collect())
+    }
+}
+
+// Lowercases, sorts and deduplicates, dropping entries that are not words of
+// the game. In English lists, capitalized entries such as "Paris" are the
+// proper nouns of system word lists and are dropped too; all-caps lists are
+// fine. Other languages, German first, capitalize common nouns as well.
+fn normalize_words(config: &GameConfig, entries: Vec<String>) -> Vec<String> {
+    let drop_capitalized = config.alphabet == Alphabet::english();
+    let mut words: Vec<String> = entries
+        .iter()
+        .map(|entry| entry.trim())
+        .filter(|word| {
+            !(drop_capitalized && word.starts_with(char::is_uppercase) && word.contains(char::is_lowercase))
+        })
+        .filter_map(|word| config.alphabet.split(word).ok())
+        .filter(|letters| letters.len() == config.word_len)
+        .map(|letters| config.alphabet.spell(&letters))
+        .collect();
+    words.sort();
+    words.dedup();
+    words
+}
+
+fn no_words(config: &GameConfig) -> ZkWordleError {
+    ZkWordleError::Dictionary(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("no words found for {}", config.shape_name()),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feedback::tests::letters;
+    use curve25519_dalek::scalar::Scalar;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn every_format_reads_the_same_words() {
+        let text = "crane\nSLATE\nParis\n  crane \nkebabs\nnaïve\n\nabbey\n";
+        let json = r#"["crane", "SLATE", "Paris", "crane", "kebabs", "naïve", "abbey"]"#;
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+        gzip.write_all(json.as_bytes()).unwrap();
+        let gzip = gzip.finish().unwrap();
+
+        let config = GameConfig::default();
+        for bytes in [text.as_bytes(), json.as_bytes(), &gzip] {
+            assert_eq!(
+                normalize_words(&config, parse_words(bytes).unwrap()),
+                words(&["abbey", "crane", "slate"])
+            );
+        }
+        assert!(parse_words(b"[\"crane\"").is_err());
+    }
+
+    #[test]
+    fn answers_are_always_allowed() {
+        let config = GameConfig::default();
+        let dictionary = Dictionary::new(&config, words(&["slate", "crane"]), words(&["Crane", "aahed"])).unwrap();
+        assert_eq!(dictionary.answers(), &words(&["crane", "slate"])[..]);
+        assert_eq!(dictionary.allowed(), &words(&["aahed", "crane", "slate"])[..]);
+        assert!(dictionary.is_allowed("slate") && !dictionary.is_allowed("zzzzz"));
+        let allowed = dictionary.allowed_tree::<Scalar>().unwrap();
+        assert_eq!(allowed.depth(), 2);
+        assert!(allowed.absence_proof(&letters("slate")).is_none());
+        assert!(allowed.absence_proof(&letters("zzzzz")).is_some());
+        assert!(Dictionary::new(&config, words(&["Paris"]), words(&["crane"])).is_err());
+
+        let embedded = Dictionary::embedded(&config).unwrap();
+        assert!(embedded.answers().iter().all(|word| embedded.is_allowed(word)));
+        assert!(embedded.allowed().len() > embedded.answers().len());
+    }
+
+    #[test]
+    fn keeps_only_the_words_of_the_game() {
+        let list = words(&["tree", "crane", "frozen", "abaca", "abbey", "cabbage"]);
+        for (word_len, alphabet, kept) in [
+            (4, Alphabet::english(), "tree"),
+            (5, Alphabet::english(), "abaca abbey crane"),
+            (6, Alphabet::english(), "frozen"),
+            (5, "abc".parse().unwrap(), "abaca"),
+        ] {
+            let config = GameConfig::new(word_len, alphabet, Some(6)).unwrap();
+            assert_eq!(normalize_words(&config, list.clone()).join(" "), kept);
+        }
+        for word_len in 4..=7 {
+            let config = GameConfig::new(word_len, Alphabet::english(), Some(6)).unwrap();
+            assert!(Dictionary::embedded(&config).unwrap().answers().len() > 100);
+        }
+    }
+
+    #[test]
+    fn reads_unicode_word_lists() {
+        let german = GameConfig::new(5, Alphabet::german(), Some(6)).unwrap();
+        let list = words(&["Straße", "GRÜßE", "grüße", "mädchen", "BÄUME", "Größe"]);
+        assert_eq!(normalize_words(&german, list).join(" "), "bäume größe grüße");
+
+        // decomposed and accented spellings end up as the same word
+        let spanish = GameConfig::new(5, Alphabet::spanish(), Some(6)).unwrap();
+        let list = words(&["nin\u{303}os", "NIÑOS", "árbol", "ARBOL", "pingüino"]);
+        assert_eq!(normalize_words(&spanish, list).join(" "), "arbol niños");
+
+        let greek = GameConfig::new(5, Alphabet::greek(), Some(6)).unwrap();
+        let json = r#"["λόγος", "ΛΟΓΟΣ", "Αθήνα", "μήλο", "θάλασσα", "ψυχές"]"#;
+        assert_eq!(normalize_words(&greek, parse_words(json.as_bytes()).unwrap()).join(" "), "αθηνα λογοσ ψυχεσ");
+
+        assert!(Dictionary::embedded(&greek).is_err());
+    }
+}
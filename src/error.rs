@@ -0,0 +1,65 @@
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum ZkWordleError {
+    InvalidGuess(String),
+    InvalidWord(String),
+    InvalidCommitment,
+    InvalidRoot,
+    CommitmentMismatch,
+    InvalidSecret(String),
+    InvalidFeedback(String),
+    InvalidConfig(String),
+    GameOver,
+    InvalidParams(String),
+    MalformedProof(String),
+    CircuitConstruction(String),
+    UnsatisfiedConstraint { index: usize, name: String },
+    Dictionary(io::Error),
+    Serialization(bincode::Error),
+}
+
+impl fmt::Display for ZkWordleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ZkWordleError::InvalidGuess(reason) => write!(f, "invalid guess: {}", reason),
+            ZkWordleError::InvalidWord(reason) => write!(f, "invalid hidden word: {}", reason),
+            ZkWordleError::InvalidCommitment => {
+                write!(f, "commitment is not the hex encoding of a canonical field element")
+            }
+            ZkWordleError::InvalidRoot => {
+                write!(f, "word list root is not the hex encoding of a canonical field element")
+            }
+            ZkWordleError::CommitmentMismatch => write!(f, "proof is for another commitment than the game's"),
+            ZkWordleError::InvalidSecret(reason) => write!(f, "invalid game secret: {}", reason),
+            ZkWordleError::InvalidFeedback(reason) => write!(f, "invalid feedback: {}", reason),
+            ZkWordleError::InvalidConfig(reason) => write!(f, "invalid game configuration: {}", reason),
+            ZkWordleError::GameOver => write!(f, "the game is already over"),
+            ZkWordleError::InvalidParams(reason) => write!(f, "invalid parameter file: {}", reason),
+            ZkWordleError::MalformedProof(reason) => write!(f, "malformed proof: {}", reason),
+            ZkWordleError::CircuitConstruction(reason) => write!(f, "failed to build the circuit: {}", reason),
+            ZkWordleError::UnsatisfiedConstraint { index, name } => {
+                write!(f, "witness fails constraint {} ({})", index, name)
+            }
+            ZkWordleError::Dictionary(err) => write!(f, "failed to load the dictionary: {}", err),
+            ZkWordleError::Serialization(err) => write!(f, "serialization failed: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ZkWordleError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ZkWordleError::Dictionary(err) => Some(err),
+            ZkWordleError::Serialization(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ZkWordleError {
+    fn from(err: io::Error) -> Self {
+        ZkWordleError::Dictionary(err)
+    }
+}
@@ -0,0 +1,184 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::ZkWordleError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LetterFeedback {
+    Gray,
+    Yellow,
+    Green,
+}
+
+// Per-position feedback for one guess
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Feedback(Vec<LetterFeedback>);
+
+impl Feedback {
+    pub fn from_letters(letters: Vec<LetterFeedback>) -> Self {
+        Feedback(letters)
+    }
+
+    // Standard Wordle scoring: greens first, then yellows from left to right for as
+    // long as unmatched copies of the letter remain in the hidden word. The circuit
+    // enforces the same rule through the available/prior counts in WordleCircuit.
+    pub(crate) fn score(hidden_word: &[u8], guess: &[u8]) -> Self {
+        let mut feedback = vec![LetterFeedback::Gray; guess.len()];
+        let mut remaining = [0usize; u8::MAX as usize + 1];
+
+        for (i, (&h, &g)) in hidden_word.iter().zip(guess.iter()).enumerate() {
+            if h == g {
+                feedback[i] = LetterFeedback::Green;
+            } else {
+                remaining[h as usize] += 1;
+            }
+        }
+
+        for (i, &g) in guess.iter().enumerate() {
+            if feedback[i] != LetterFeedback::Green && remaining[g as usize] > 0 {
+                feedback[i] = LetterFeedback::Yellow;
+                remaining[g as usize] -= 1;
+            }
+        }
+
+        Feedback(feedback)
+    }
+
+    pub fn letters(&self) -> &[LetterFeedback] {
+        &self.0
+    }
+
+    pub fn is_win(&self) -> bool {
+        self.0.iter().all(|&f| f == LetterFeedback::Green)
+    }
+}
+
+// One character per letter: G for green, Y for yellow, . for gray
+impl fmt::Display for Feedback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for letter in self.0.iter() {
+            let c = match letter {
+                LetterFeedback::Gray => '.',
+                LetterFeedback::Yellow => 'Y',
+                LetterFeedback::Green => 'G',
+            };
+            write!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
+// Accepts lowercase too; the length is checked against the circuit by the verifier
+impl FromStr for Feedback {
+    type Err = ZkWordleError;
+
+    fn from_str(s: &str) -> Result<Self, ZkWordleError> {
+        s.trim()
+            .chars()
+            .map(|c| match c.to_ascii_uppercase() {
+                '.' => Ok(LetterFeedback::Gray),
+                'Y' => Ok(LetterFeedback::Yellow),
+                'G' => Ok(LetterFeedback::Green),
+                _ => Err(ZkWordleError::InvalidFeedback(format!("'{}' is not one of G, Y or .", c))),
+            })
+            .collect::<Result<_, _>>()
+            .map(Feedback)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::config::{GameConfig, DEFAULT_WORD_LEN};
+    use proptest::prelude::*;
+    use LetterFeedback::{Gray, Green, Yellow};
+
+    pub(crate) fn letters(word: &str) -> Vec<u8> {
+        word.bytes().map(|c| c - b'a').collect()
+    }
+
+    // Scoring as a person would do it by hand, independent of Feedback::score:
+    // mark the greens, then let each remaining guess letter claim the leftmost
+    // unclaimed, non-green copy of itself in the hidden word
+    pub(crate) fn reference_score(hidden_word: &[u8], guess: &[u8]) -> Vec<LetterFeedback> {
+        let mut feedback = vec![Gray; guess.len()];
+        let mut claimed = vec![false; hidden_word.len()];
+        for i in 0..guess.len() {
+            if guess[i] == hidden_word[i] {
+                feedback[i] = Green;
+                claimed[i] = true;
+            }
+        }
+        for i in 0..guess.len() {
+            if feedback[i] == Green {
+                continue;
+            }
+            if let Some(j) = (0..hidden_word.len()).find(|&j| !claimed[j] && hidden_word[j] == guess[i]) {
+                claimed[j] = true;
+                feedback[i] = Yellow;
+            }
+        }
+        feedback
+    }
+
+    // Mostly words over a three-letter alphabet, so repeated letters and letters
+    // shared between hidden word and guess are the common case rather than the rare one
+    fn word() -> impl Strategy<Value = Vec<u8>> {
+        let config = GameConfig::default();
+        prop_oneof![
+            3 => prop::collection::vec(0u8..3, config.word_len),
+            1 => prop::collection::vec(0..config.alphabet.size() as u8, config.word_len),
+        ]
+    }
+
+    // (hidden word, guess), including guesses that are anagrams of the hidden word
+    pub(crate) fn word_pair() -> impl Strategy<Value = (Vec<u8>, Vec<u8>)> {
+        prop_oneof![
+            (word(), word()),
+            word().prop_flat_map(|hidden_word| (Just(hidden_word.clone()), Just(hidden_word).prop_shuffle())),
+        ]
+    }
+
+    // (hidden word, guess, expected feedback)
+    pub(crate) const TRICKY_PAIRS: &[(&str, &str, [LetterFeedback; DEFAULT_WORD_LEN])] = &[
+        ("abide", "speed", [Gray, Gray, Yellow, Gray, Yellow]),
+        ("geese", "eerie", [Yellow, Green, Gray, Gray, Green]),
+        ("eerie", "geese", [Gray, Green, Yellow, Gray, Green]),
+        ("lever", "eerie", [Yellow, Green, Yellow, Gray, Gray]),
+        ("crane", "eerie", [Gray, Gray, Yellow, Gray, Green]),
+        ("abbey", "babes", [Yellow, Yellow, Green, Green, Gray]),
+        ("kebab", "abbey", [Yellow, Yellow, Green, Yellow, Gray]),
+        ("robot", "floor", [Gray, Gray, Yellow, Green, Yellow]),
+        ("mummy", "mamma", [Green, Gray, Green, Green, Gray]),
+        ("sissy", "sassy", [Green, Gray, Green, Green, Green]),
+        ("crane", "crane", [Green, Green, Green, Green, Green]),
+    ];
+
+    #[test]
+    fn score_handles_repeated_letters() {
+        for (hidden_word, guess, expected) in TRICKY_PAIRS {
+            assert_eq!(
+                Feedback::score(&letters(hidden_word), &letters(guess)).letters(),
+                expected,
+                "{} against {}",
+                guess,
+                hidden_word
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn score_matches_the_reference((hidden_word, guess) in word_pair()) {
+            let scored = Feedback::score(&hidden_word, &guess);
+            prop_assert_eq!(scored.letters(), &reference_score(&hidden_word, &guess)[..]);
+            prop_assert_eq!(scored.to_string().parse::<Feedback>().unwrap(), scored);
+        }
+    }
+
+    #[test]
+    fn parse_reads_one_character_per_letter() {
+        assert_eq!("gY..G".parse::<Feedback>().unwrap().letters(), &[Green, Yellow, Gray, Gray, Green]);
+        assert!(matches!("GY-.G".parse::<Feedback>(), Err(ZkWordleError::InvalidFeedback(_))));
+    }
+}
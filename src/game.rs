@@ -0,0 +1,123 @@
+use crate::config::GameConfig;
+use crate::error::ZkWordleError;
+use crate::feedback::Feedback;
+
+// How a game ended. A player who stops before finding the word, which is the
+// only way out of a practice game, loses too.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameResult {
+    Won { guesses: usize },
+    Lost { guesses: usize },
+}
+
+impl GameResult {
+    pub fn guesses(&self) -> usize {
+        match *self {
+            GameResult::Won { guesses } | GameResult::Lost { guesses } => guesses,
+        }
+    }
+}
+
+// Progress of one game against the config's guess limit, kept by whoever runs
+// the game from the feedback of each turn
+#[derive(Clone, Debug)]
+pub struct Game {
+    config: GameConfig,
+    guesses: usize,
+    result: Option<GameResult>,
+}
+
+impl Game {
+    pub fn new(config: &GameConfig) -> Self {
+        Game {
+            config: config.clone(),
+            guesses: 0,
+            result: None,
+        }
+    }
+
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    pub fn guesses(&self) -> usize {
+        self.guesses
+    }
+
+    // None in practice games. A config that skipped validation may allow no
+    // guesses at all, which ends the game on the first one.
+    pub fn guesses_left(&self) -> Option<usize> {
+        self.config.max_guesses.map(|max| max.saturating_sub(self.guesses))
+    }
+
+    pub fn result(&self) -> Option<GameResult> {
+        self.result
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.result.is_some()
+    }
+
+    // Counts a guess with its feedback, returning the result if that ended the game
+    pub fn record(&mut self, feedback: &Feedback) -> Result<Option<GameResult>, ZkWordleError> {
+        if self.is_over() {
+            return Err(ZkWordleError::GameOver);
+        }
+        self.guesses += 1;
+        if feedback.is_win() {
+            self.result = Some(GameResult::Won { guesses: self.guesses });
+        } else if self.guesses_left() == Some(0) {
+            self.result = Some(GameResult::Lost { guesses: self.guesses });
+        }
+        Ok(self.result)
+    }
+
+    // Ends the game early, as a loss, unless it is over already
+    pub fn give_up(&mut self) -> GameResult {
+        *self.result.get_or_insert(GameResult::Lost { guesses: self.guesses })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feedback::tests::letters;
+
+    fn feedback(hidden_word: &str, guess: &str) -> Feedback {
+        Feedback::score(&letters(hidden_word), &letters(guess))
+    }
+
+    #[test]
+    fn games_end_on_the_word_or_the_last_guess() {
+        let config = GameConfig::new(5, Default::default(), Some(3)).unwrap();
+        let mut game = Game::new(&config);
+        assert_eq!(game.record(&feedback("crane", "slate")).unwrap(), None);
+        assert_eq!(game.guesses_left(), Some(2));
+        assert_eq!(game.record(&feedback("crane", "crane")).unwrap(), Some(GameResult::Won { guesses: 2 }));
+        assert!(matches!(game.record(&feedback("crane", "crane")), Err(ZkWordleError::GameOver)));
+
+        let mut game = Game::new(&config);
+        for _ in 0..2 {
+            assert_eq!(game.record(&feedback("crane", "slate")).unwrap(), None);
+        }
+        assert_eq!(game.record(&feedback("crane", "slate")).unwrap(), Some(GameResult::Lost { guesses: 3 }));
+        assert_eq!(game.give_up(), GameResult::Lost { guesses: 3 });
+
+        let unvalidated = GameConfig {
+            max_guesses: Some(0),
+            ..GameConfig::default()
+        };
+        let mut game = Game::new(&unvalidated);
+        assert_eq!(game.record(&feedback("crane", "slate")).unwrap(), Some(GameResult::Lost { guesses: 1 }));
+
+        // practice games only end on the word, or when the player stops
+        let practice = GameConfig::new(5, Default::default(), None).unwrap();
+        let mut game = Game::new(&practice);
+        for _ in 0..100 {
+            assert_eq!(game.record(&feedback("crane", "slate")).unwrap(), None);
+        }
+        assert_eq!(game.guesses_left(), None);
+        assert_eq!(game.give_up(), GameResult::Lost { guesses: 100 });
+        assert!(game.is_over());
+    }
+}
@@ -0,0 +1,262 @@
+// Groth16 backend over BLS12-381.
+//
+// Proofs are a constant 192 bytes, paid for with a circuit-specific trusted
+// setup (Groth16Params::generate).
+
+use bellman::groth16::{self, Parameters, PreparedVerifyingKey, Proof, VerifyingKey};
+use bellman::{SynthesisError, VerificationError};
+use bls12_381::{Bls12, Scalar as BlsScalar};
+use rand::thread_rng;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::circuit::WordleCircuit;
+use crate::config::GameConfig;
+use crate::error::ZkWordleError;
+use crate::proof_system::ProofSystem;
+
+fn circuit_error(err: SynthesisError) -> ZkWordleError {
+    ZkWordleError::CircuitConstruction(err.to_string())
+}
+
+fn io_error(err: std::io::Error) -> ZkWordleError {
+    ZkWordleError::InvalidParams(err.to_string())
+}
+
+// Unlike Spartan's, Groth16 parameters do not record the circuit they were
+// generated for. The number of public inputs (the commitment and the answer
+// tree root, then a guess letter and two feedback bits per letter) at least
+// tells word lengths apart.
+fn check_inputs(config: &GameConfig, vk: &VerifyingKey<Bls12>) -> Result<(), ZkWordleError> {
+    let expected = 1 + 2 + 3 * config.word_len;
+    if vk.ic.len() != expected {
+        return Err(ZkWordleError::InvalidParams(format!(
+            "parameters have {} public inputs, the circuit for {} needs {}",
+            vk.ic.len().saturating_sub(1),
+            config.shape_name(),
+            expected - 1
+        )));
+    }
+    Ok(())
+}
+
+// Alphabets of one size share the circuit, and trees of any depth need the
+// same inputs, so the files start with the id of the alphabet the keys were
+// generated for and the depth of their answer tree
+fn read_variant<R: Read>(config: &GameConfig, reader: &mut R) -> Result<(), ZkWordleError> {
+    let mut id = [0u8; 32];
+    reader.read_exact(&mut id).map_err(io_error)?;
+    if id != config.alphabet.id() {
+        return Err(ZkWordleError::InvalidParams(format!(
+            "parameters are for another alphabet than {}",
+            config.alphabet
+        )));
+    }
+    let mut depth = [0u8];
+    reader.read_exact(&mut depth).map_err(io_error)?;
+    if depth[0] as usize != config.answer_tree_depth {
+        return Err(ZkWordleError::InvalidParams(format!(
+            "parameters are for answer trees of depth {}, not {}",
+            depth[0], config.answer_tree_depth
+        )));
+    }
+    Ok(())
+}
+
+fn write_variant<W: Write>(alphabet: &[u8; 32], answer_tree_depth: u8, writer: &mut W) -> Result<(), ZkWordleError> {
+    writer.write_all(alphabet).map_err(io_error)?;
+    writer.write_all(&[answer_tree_depth]).map_err(io_error)
+}
+
+// Proving parameters from the trusted setup, which also contain the verifying key
+pub struct Groth16Params {
+    alphabet: [u8; 32],
+    answer_tree_depth: u8,
+    params: Parameters<Bls12>,
+}
+
+pub struct Groth16VerifyingKey {
+    alphabet: [u8; 32],
+    answer_tree_depth: u8,
+    vk: VerifyingKey<Bls12>,
+    pvk: PreparedVerifyingKey<Bls12>,
+}
+
+impl Groth16Params {
+    // Runs the setup with fresh randomness; whoever runs it must discard the toxic waste
+    pub fn generate(config: &GameConfig) -> Result<Self, ZkWordleError> {
+        config.validate()?;
+        let params =
+            groth16::generate_random_parameters::<Bls12, _, _>(WordleCircuit::blank(config), &mut thread_rng())
+                .map_err(circuit_error)?;
+        Ok(Groth16Params {
+            alphabet: config.alphabet.id(),
+            answer_tree_depth: config.answer_tree_depth as u8,
+            params,
+        })
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), ZkWordleError> {
+        write_variant(&self.alphabet, self.answer_tree_depth, &mut writer)?;
+        self.params.write(writer).map_err(io_error)
+    }
+
+    pub fn read<R: Read>(config: &GameConfig, mut reader: R) -> Result<Self, ZkWordleError> {
+        read_variant(config, &mut reader)?;
+        let params = Parameters::read(reader, true).map_err(io_error)?;
+        check_inputs(config, &params.vk)?;
+        Ok(Groth16Params {
+            alphabet: config.alphabet.id(),
+            answer_tree_depth: config.answer_tree_depth as u8,
+            params,
+        })
+    }
+
+    pub fn verifying_key(&self) -> Groth16VerifyingKey {
+        Groth16VerifyingKey::new(self.alphabet, self.answer_tree_depth, self.params.vk.clone())
+    }
+}
+
+impl Groth16VerifyingKey {
+    fn new(alphabet: [u8; 32], answer_tree_depth: u8, vk: VerifyingKey<Bls12>) -> Self {
+        let pvk = groth16::prepare_verifying_key(&vk);
+        Groth16VerifyingKey {
+            alphabet,
+            answer_tree_depth,
+            vk,
+            pvk,
+        }
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), ZkWordleError> {
+        write_variant(&self.alphabet, self.answer_tree_depth, &mut writer)?;
+        self.vk.write(writer).map_err(io_error)
+    }
+
+    pub fn read<R: Read>(config: &GameConfig, mut reader: R) -> Result<Self, ZkWordleError> {
+        read_variant(config, &mut reader)?;
+        let vk = VerifyingKey::read(reader).map_err(io_error)?;
+        check_inputs(config, &vk)?;
+        Ok(Groth16VerifyingKey::new(
+            config.alphabet.id(),
+            config.answer_tree_depth as u8,
+            vk,
+        ))
+    }
+}
+
+pub struct Groth16;
+
+impl ProofSystem for Groth16 {
+    const NAME: &'static str = "groth16";
+
+    type Field = BlsScalar;
+    type Params = Groth16Params;
+    type VerifyingKey = Groth16VerifyingKey;
+    type Proof = Proof<Bls12>;
+
+    fn params_path(config: &GameConfig) -> PathBuf {
+        PathBuf::from(format!("params/wordle-{}-groth16.bin", config.shape_name()))
+    }
+
+    fn setup(config: &GameConfig) -> Result<Groth16Params, ZkWordleError> {
+        Groth16Params::generate(config)
+    }
+
+    fn verifying_key(params: &Groth16Params) -> Arc<Groth16VerifyingKey> {
+        Arc::new(params.verifying_key())
+    }
+
+    fn prove(params: &Groth16Params, circuit: WordleCircuit<BlsScalar>) -> Result<Proof<Bls12>, ZkWordleError> {
+        groth16::create_random_proof(circuit, &params.params, &mut thread_rng()).map_err(circuit_error)
+    }
+
+    fn verify(key: &Groth16VerifyingKey, inputs: &[BlsScalar], proof: &Proof<Bls12>) -> Result<bool, ZkWordleError> {
+        match groth16::verify_proof(&key.pvk, proof, inputs) {
+            Ok(()) => Ok(true),
+            Err(VerificationError::InvalidProof) => Ok(false),
+            Err(err) => Err(ZkWordleError::CircuitConstruction(err.to_string())),
+        }
+    }
+
+    fn write_params<W: Write>(params: &Groth16Params, writer: W) -> Result<(), ZkWordleError> {
+        params.write(writer)
+    }
+
+    fn read_params<R: Read>(config: &GameConfig, reader: R) -> Result<Groth16Params, ZkWordleError> {
+        Groth16Params::read(config, reader)
+    }
+
+    fn write_verifying_key<W: Write>(key: &Groth16VerifyingKey, writer: W) -> Result<(), ZkWordleError> {
+        key.write(writer)
+    }
+
+    fn read_verifying_key<R: Read>(config: &GameConfig, reader: R) -> Result<Groth16VerifyingKey, ZkWordleError> {
+        Groth16VerifyingKey::read(config, reader)
+    }
+
+    fn proof_to_bytes(proof: &Proof<Bls12>) -> Result<Vec<u8>, ZkWordleError> {
+        let mut bytes = Vec::new();
+        proof
+            .write(&mut bytes)
+            .map_err(|e| ZkWordleError::Serialization(Box::new(bincode::ErrorKind::Io(e))))?;
+        Ok(bytes)
+    }
+
+    fn proof_from_bytes(bytes: &[u8]) -> Result<Proof<Bls12>, ZkWordleError> {
+        let mut bytes = bytes;
+        let proof = Proof::<Bls12>::read(&mut bytes).map_err(|e| ZkWordleError::MalformedProof(e.to_string()))?;
+        if !bytes.is_empty() {
+            return Err(ZkWordleError::MalformedProof("trailing bytes after proof".to_string()));
+        }
+        Ok(proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::Alphabet;
+    use crate::feedback::tests::letters;
+    use crate::guess::Guess;
+    use crate::merkle::tests::answer_tree;
+    use crate::prover::Prover;
+    use crate::verifier::Verifier;
+
+    #[test]
+    fn proves_and_verifies_a_turn() {
+        let config = GameConfig::default();
+        let params = Arc::new(Groth16Params::generate(&config).unwrap());
+        let answers = answer_tree(&config, &["crane", "eerie"]);
+        let prover = Prover::<Groth16>::new(&config, &answers, letters("eerie"), params.clone()).unwrap();
+        let verifier =
+            Verifier::<Groth16>::new(&config, prover.commitment(), answers.root(), Groth16::verifying_key(&params));
+
+        let guess = Guess::parse(&config, "geese").unwrap();
+        let (feedback, proof) = prover.prove(&guess).unwrap();
+        assert_eq!(proof.as_bytes().len(), 192);
+        assert!(verifier.verify(&guess, &feedback, &proof).unwrap());
+
+        let other = Guess::parse(&config, "crane").unwrap();
+        assert!(!verifier.verify(&other, &feedback, &proof).unwrap());
+
+        // the key only fits games with as many letters, from the same alphabet,
+        // drawn from answer trees of the same depth
+        let mut bytes = Vec::new();
+        Groth16::write_verifying_key(&params.verifying_key(), &mut bytes).unwrap();
+        assert!(Groth16::read_verifying_key(&config, &bytes[..]).is_ok());
+        let six = GameConfig::new(6, Alphabet::english(), Some(6)).unwrap();
+        let reversed = GameConfig::new(5, "zyxwvutsrqponmlkjihgfedcba".parse().unwrap(), Some(6)).unwrap();
+        let shallow = GameConfig {
+            answer_tree_depth: 4,
+            ..GameConfig::default()
+        };
+        for other in [six, reversed, shallow] {
+            assert!(matches!(
+                Groth16::read_verifying_key(&other, &bytes[..]),
+                Err(ZkWordleError::InvalidParams(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::fmt;
+
+use crate::config::GameConfig;
+use crate::dictionary::Dictionary;
+use crate::error::ZkWordleError;
+
+// A guess as indices into the game's alphabet, along with its spelling
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Guess {
+    letters: Vec<u8>,
+    word: String,
+}
+
+impl Guess {
+    pub fn from_letters(config: &GameConfig, letters: Vec<u8>) -> Result<Self, ZkWordleError> {
+        check_letters(config, &letters).map_err(ZkWordleError::InvalidGuess)?;
+        let word = config.alphabet.spell(&letters);
+        Ok(Guess { letters, word })
+    }
+
+    // Trims and lowercases player input, rejecting anything that is not
+    // word_len letters of the alphabet before it reaches the circuit
+    pub fn parse(config: &GameConfig, input: &str) -> Result<Self, ZkWordleError> {
+        let letters = config.alphabet.split(input.trim()).map_err(ZkWordleError::InvalidGuess)?;
+        if letters.len() != config.word_len {
+            return Err(ZkWordleError::InvalidGuess(format!(
+                "guesses must have {} letters, got {}",
+                config.word_len,
+                letters.len()
+            )));
+        }
+        Guess::from_letters(config, letters)
+    }
+
+    // Like parse, but also requires the word to be an allowed guess
+    pub fn parse_in(input: &str, dictionary: &Dictionary) -> Result<Self, ZkWordleError> {
+        let guess = Guess::parse(dictionary.config(), input)?;
+        let word = guess.to_string();
+        if !dictionary.is_allowed(&word) {
+            return Err(ZkWordleError::InvalidGuess(format!("'{}' is not in the word list", word)));
+        }
+        Ok(guess)
+    }
+
+    pub fn letters(&self) -> &[u8] {
+        &self.letters
+    }
+}
+
+impl fmt::Display for Guess {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.word)
+    }
+}
+
+// Shared by guesses and hidden words: word_len letters, each in the alphabet
+pub(crate) fn check_letters(config: &GameConfig, letters: &[u8]) -> Result<(), String> {
+    if letters.len() != config.word_len {
+        return Err(format!("expected {} letters, got {}", config.word_len, letters.len()));
+    }
+    if let Some(&c) = letters.iter().find(|&&c| c as usize >= config.alphabet.size()) {
+        return Err(format!("letter index {} is outside the alphabet", c));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::Alphabet;
+
+    #[test]
+    fn parse_normalizes_case_and_whitespace() {
+        let guess = Guess::parse(&GameConfig::default(), "  CrAnE\n").unwrap();
+        assert_eq!(guess.letters(), &[2, 17, 0, 13, 4]);
+        assert_eq!(guess.to_string(), "crane");
+    }
+
+    #[test]
+    fn parse_rejects_bad_input() {
+        for input in ["", "cran", "cranes", "cr4ne", "cr-ne", "crâne"] {
+            assert!(
+                matches!(Guess::parse(&GameConfig::default(), input), Err(ZkWordleError::InvalidGuess(_))),
+                "{:?}",
+                input
+            );
+        }
+
+        // the length and the letters both come from the game
+        let six = GameConfig::new(6, "abcdefghijklmnopqrst".parse().unwrap(), Some(6)).unwrap();
+        assert!(Guess::parse(&six, "cranes").is_ok());
+        assert!(Guess::parse(&six, "crane").is_err());
+        assert!(Guess::parse(&six, "frozen").is_err());
+    }
+
+    #[test]
+    fn parse_counts_graphemes() {
+        let spanish = GameConfig::new(5, Alphabet::spanish(), Some(6)).unwrap();
+        let guess = Guess::parse(&spanish, " NIÑOS ").unwrap();
+        assert_eq!(guess.letters(), &[13, 8, 14, 15, 19]);
+        assert_eq!(guess.to_string(), "niños");
+        assert!(Guess::parse(&spanish, "niño").is_err());
+        assert_eq!(Guess::parse(&spanish, "Árbol").unwrap().to_string(), "arbol");
+        assert!(Guess::parse(&GameConfig::default(), "árbol").is_err());
+    }
+
+    #[test]
+    fn parse_in_checks_the_dictionary() {
+        let dictionary =
+            Dictionary::new(&GameConfig::default(), vec!["crane".to_string()], vec!["slate".to_string()]).unwrap();
+        assert!(Guess::parse_in("SLATE", &dictionary).is_ok());
+        assert!(matches!(
+            Guess::parse_in("xxxxx", &dictionary),
+            Err(ZkWordleError::InvalidGuess(_))
+        ));
+    }
+}
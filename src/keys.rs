@@ -0,0 +1,135 @@
+use libspartan::{ComputationCommitment, ComputationDecommitment, Instance, NIZKGens, SNARKGens, SNARK};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use crate::config::GameConfig;
+use crate::error::ZkWordleError;
+use crate::r1cs::{game_constraints, GameR1cs};
+
+// Public parameters for checking proofs: the generators and the commitment to
+// the encoded circuit. Both depend only on the circuit shape, so one key serves
+// every turn of every game of the same variant. The matrices are kept so the
+// key can be written back out as a params file.
+pub struct VerifyingKey {
+    pub(crate) config: GameConfig,
+    pub(crate) gens: SNARKGens,
+    pub(crate) comm: ComputationCommitment,
+    pub(crate) r1cs: GameR1cs,
+}
+
+// Everything the host needs to prove a turn, including the verifying key
+pub struct ProvingKey {
+    pub(crate) inst: Instance,
+    pub(crate) decomm: ComputationDecommitment,
+    pub(crate) vk: Arc<VerifyingKey>,
+}
+
+// Key for libspartan's NIZK, which skips encoding the circuit: setup is only
+// the generators, but verifying evaluates the whole instance again. Prover and
+// verifier hold the same key.
+pub struct NizkKey {
+    pub(crate) config: GameConfig,
+    pub(crate) inst: Instance,
+    pub(crate) gens: NIZKGens,
+    pub(crate) r1cs: GameR1cs,
+}
+
+// Keys generated so far in this process, by word length, alphabet id and
+// answer tree depth
+type Shape = (usize, [u8; 32], usize);
+static SHARED_PROVING_KEYS: Mutex<BTreeMap<Shape, Arc<ProvingKey>>> = Mutex::new(BTreeMap::new());
+static SHARED_VERIFYING_KEYS: Mutex<BTreeMap<Shape, Arc<VerifyingKey>>> = Mutex::new(BTreeMap::new());
+
+fn shape(config: &GameConfig) -> Shape {
+    (config.word_len, config.alphabet.id(), config.answer_tree_depth)
+}
+
+fn encode(r1cs: &GameR1cs) -> Result<(Instance, SNARKGens, ComputationCommitment, ComputationDecommitment), ZkWordleError> {
+    let inst = r1cs.instance()?;
+    let gens = SNARKGens::new(r1cs.num_cons, r1cs.num_vars, r1cs.num_inputs, r1cs.num_non_zero_entries());
+    let (comm, decomm) = SNARK::encode(&inst, &gens);
+    Ok((inst, gens, comm, decomm))
+}
+
+impl ProvingKey {
+    pub fn generate(config: &GameConfig) -> Result<Self, ZkWordleError> {
+        config.validate()?;
+        ProvingKey::from_r1cs(config, &game_constraints(config)?)
+    }
+
+    pub(crate) fn from_r1cs(config: &GameConfig, r1cs: &GameR1cs) -> Result<Self, ZkWordleError> {
+        let (inst, gens, comm, decomm) = encode(r1cs)?;
+        Ok(ProvingKey {
+            inst,
+            decomm,
+            vk: Arc::new(VerifyingKey {
+                config: config.clone(),
+                gens,
+                comm,
+                r1cs: r1cs.clone(),
+            }),
+        })
+    }
+
+    // Generated on first use and reused for the rest of the process
+    pub fn shared(config: &GameConfig) -> Result<Arc<Self>, ZkWordleError> {
+        let mut keys = SHARED_PROVING_KEYS.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(pk) = keys.get(&shape(config)) {
+            return Ok(pk.clone());
+        }
+        let pk = Arc::new(ProvingKey::generate(config)?);
+        keys.insert(shape(config), pk.clone());
+        Ok(pk)
+    }
+
+    pub fn verifying_key(&self) -> Arc<VerifyingKey> {
+        self.vk.clone()
+    }
+}
+
+impl VerifyingKey {
+    pub fn generate(config: &GameConfig) -> Result<Self, ZkWordleError> {
+        config.validate()?;
+        VerifyingKey::from_r1cs(config, &game_constraints(config)?)
+    }
+
+    pub(crate) fn from_r1cs(config: &GameConfig, r1cs: &GameR1cs) -> Result<Self, ZkWordleError> {
+        let (_, gens, comm, _) = encode(r1cs)?;
+        Ok(VerifyingKey {
+            config: config.clone(),
+            gens,
+            comm,
+            r1cs: r1cs.clone(),
+        })
+    }
+
+    // Prefers the verifying key of an already generated shared proving key
+    pub fn shared(config: &GameConfig) -> Result<Arc<Self>, ZkWordleError> {
+        if let Some(pk) = SHARED_PROVING_KEYS.lock().unwrap_or_else(|e| e.into_inner()).get(&shape(config)) {
+            return Ok(pk.verifying_key());
+        }
+        let mut keys = SHARED_VERIFYING_KEYS.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(vk) = keys.get(&shape(config)) {
+            return Ok(vk.clone());
+        }
+        let vk = Arc::new(VerifyingKey::generate(config)?);
+        keys.insert(shape(config), vk.clone());
+        Ok(vk)
+    }
+}
+
+impl NizkKey {
+    pub fn generate(config: &GameConfig) -> Result<Self, ZkWordleError> {
+        config.validate()?;
+        NizkKey::from_r1cs(config, &game_constraints(config)?)
+    }
+
+    pub(crate) fn from_r1cs(config: &GameConfig, r1cs: &GameR1cs) -> Result<Self, ZkWordleError> {
+        Ok(NizkKey {
+            config: config.clone(),
+            inst: r1cs.instance()?,
+            gens: NIZKGens::new(r1cs.num_cons, r1cs.num_vars, r1cs.num_inputs),
+            r1cs: r1cs.clone(),
+        })
+    }
+}
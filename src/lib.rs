@@ -0,0 +1,54 @@
+// Zero-knowledge Wordle: the host commits to a hidden word up front and proves
+// that the feedback for every guess is consistent with that commitment.
+
+#[cfg(not(any(feature = "spartan", feature = "groth16")))]
+compile_error!("enable at least one proof system feature: spartan or groth16");
+
+mod alphabet;
+mod circuit;
+mod commitment;
+mod config;
+mod dictionary;
+mod error;
+mod feedback;
+mod game;
+#[cfg(feature = "groth16")]
+mod groth16;
+mod guess;
+#[cfg(feature = "spartan")]
+mod keys;
+mod merkle;
+mod mimc;
+#[cfg(feature = "spartan")]
+mod params;
+mod proof_system;
+mod prover;
+mod r1cs;
+#[cfg(test)]
+mod soundness;
+#[cfg(feature = "spartan")]
+mod spartan;
+mod verifier;
+
+pub use alphabet::{Alphabet, MAX_ALPHABET_SIZE};
+pub use circuit::WordleCircuit;
+pub use commitment::{Commitment, GameSecret};
+pub use config::{GameConfig, DEFAULT_ANSWER_TREE_DEPTH, DEFAULT_WORD_LEN, MAX_ANSWER_TREE_DEPTH, MAX_WORD_LEN};
+pub use dictionary::{load_words, Dictionary};
+pub use error::ZkWordleError;
+pub use feedback::{Feedback, LetterFeedback};
+pub use game::{Game, GameResult};
+#[cfg(feature = "groth16")]
+pub use groth16::{Groth16, Groth16Params, Groth16VerifyingKey};
+pub use guess::Guess;
+#[cfg(feature = "spartan")]
+pub use keys::{NizkKey, ProvingKey, VerifyingKey};
+pub use merkle::{AbsenceProof, MerklePath, MerkleRoot, WordTree};
+#[cfg(feature = "spartan")]
+pub use params::{params_path, CircuitShape, PublicParams};
+pub use proof_system::ProofSystem;
+pub use prover::{GuessProof, Prover};
+pub use r1cs::R1csBuilder;
+#[cfg(feature = "spartan")]
+pub use spartan::{SpartanNizk, SpartanSnark};
+pub use verifier::{GameVerifier, Verifier};
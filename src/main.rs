@@ -1,206 +1,510 @@
-use curve25519_dalek::scalar::Scalar;
-use libspartan::{Instance, SNARKGens, SNARK, InputsAssignment, VarsAssignment};
-use merlin::Transcript;
 use rand::seq::SliceRandom;
 use rand::thread_rng;
-use std::fs::File;
-use std::io::{self, BufRead};
-
-use bincode;
-
-
-// Constants
-const NUM_DIGITS: usize = 5;
-const DIGIT_RANGE: usize = 26;
-
-// Circuit inputs
-struct GameInputs {
-    hidden_word: Vec<u8>,
-    guess: Vec<u8>,
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::Arc;
+use std::time::Instant;
+
+use zk_wordle::{
+    load_words, AbsenceProof, Commitment, Dictionary, Feedback, GameConfig, GameResult, GameSecret, GameVerifier, Guess,
+    GuessProof, ProofSystem, Prover, Verifier, ZkWordleError,
+};
+#[cfg(feature = "groth16")]
+use zk_wordle::Groth16;
+#[cfg(feature = "spartan")]
+use zk_wordle::{SpartanNizk, SpartanSnark};
+
+#[cfg(feature = "spartan")]
+const DEFAULT_BACKEND: &str = SpartanSnark::NAME;
+#[cfg(not(feature = "spartan"))]
+const DEFAULT_BACKEND: &str = Groth16::NAME;
+
+// Backends compiled into this binary, for the usage message
+const BACKENDS: &[&str] = &[
+    #[cfg(feature = "spartan")]
+    SpartanSnark::NAME,
+    #[cfg(feature = "spartan")]
+    SpartanNizk::NAME,
+    #[cfg(feature = "groth16")]
+    Groth16::NAME,
+];
+
+const COMMANDS: &str = "
+commands:
+  play                                          play a whole game interactively (the default)
+  commit <secret-file> [<word>]                 pick a word (random unless given), save its secret
+                                                and print the commitment; runs the setup unless the
+                                                parameter file exists, and saves the verifying key
+  prove <secret-file> <guess> <proof-file>      score a guess, print the feedback and save its proof;
+                                                for a guess that is not in the word list, save a
+                                                proof of that instead and exit 1
+  verify <commitment> <guess> <feedback> <proof-file>
+                                                check a proof against the verifying key given with
+                                                --verifying-key; exits 0 if it is valid, 1 if not
+  verify-rejection <guess> <proof-file>         check that a rejected guess is not in the word list;
+                                                exits 0 if the proof is valid, 1 if not
+  reveal <commitment> <secret-file>             check that a disclosed secret opens the commitment
+
+Feedback has one character per letter: G green, Y yellow, . gray.
+--check-witness evaluates the constraints on every witness before proving it.
+--dictionary replaces the built-in answer list and --guesses adds allowed guesses;
+either file may be plain text with one word per line or a JSON array, optionally gzipped.
+--word-length (default 5) and --alphabet pick the variant of the game. The alphabet is
+english (the default), spanish, german, greek, or the letters themselves in order, as in
+--alphabet abcdefghijklmnñopqrstuvwxyz; the built-in word lists are English only.
+Each variant has its own parameter file, which only the host needs: commit creates it and
+prove reads it. Players check proofs with the verifying key commit saves, by default next to
+the parameter file, or wherever --verifying-key says.
+Proofs also show that the hidden word is one of the answers, hashed into a Merkle tree
+whose root the player computes from the same list; --answer-tree-depth (default 12)
+makes room for up to 2^depth answers, and is part of the variant too.
+--max-guesses (default 6, or unlimited for practice) limits the turns of play.";
+
+fn usage() -> String {
+    format!(
+        "usage: zk-wordle [--backend {}] [--check-witness] [--dictionary <file>] [--guesses <file>]\n\
+         \x20                [--word-length <n>] [--alphabet <alphabet>] [--answer-tree-depth <n>]\n\
+         \x20                [--max-guesses <n>|unlimited] [--verifying-key <file>] [<command>]\n{}",
+        BACKENDS.join("|"),
+        COMMANDS
+    )
 }
 
-struct GameOutputs {
-    letter_in_word: Vec<bool>,
-    letter_correct: Vec<bool>,
+enum Command {
+    Play,
+    Commit {
+        secret: String,
+        word: Option<String>,
+    },
+    Prove {
+        secret: String,
+        guess: String,
+        proof: String,
+    },
+    Verify {
+        commitment: String,
+        guess: String,
+        feedback: String,
+        proof: String,
+        verifying_key: String,
+    },
+    VerifyRejection {
+        guess: String,
+        proof: String,
+    },
+    Reveal {
+        commitment: String,
+        secret: String,
+    },
 }
 
-// Circuit constraints
-fn game_constraints(inputs: &GameInputs, outputs: &GameOutputs) -> Instance {
-    let num_cons = NUM_DIGITS * 2;
-    let num_vars = NUM_DIGITS * 2;
-    let num_inputs = NUM_DIGITS * 2;
-    let num_non_zero_entries = NUM_DIGITS * 2;
-
-    let mut A = Vec::new();
-    let mut B = Vec::new();
-    let mut C = Vec::new();
-
-    for i in 0..NUM_DIGITS {
-        let hidden_char = inputs.hidden_word[i] as usize;
-        let guess_char = inputs.guess[i] as usize;
-
-        A.push((i, hidden_char, Scalar::from(1u8).to_bytes()));
-        B.push((i, guess_char + DIGIT_RANGE, Scalar::from(1u8).to_bytes()));
-        C.push((i, i, Scalar::from(outputs.letter_in_word[i] as u8).to_bytes()));
+struct Options {
+    backend: String,
+    config: GameConfig,
+    // evaluate the constraints on every witness before proving it
+    check_witness: bool,
+    // answer list, and further allowed guesses, instead of the built-in lists
+    dictionary: Option<String>,
+    guesses: Option<String>,
+    // the --verifying-key file, which commit writes and verify reads
+    verifying_key: Option<String>,
+    command: Command,
+}
 
-        A.push((i + NUM_DIGITS, hidden_char, Scalar::from(1u8).to_bytes()));
-        B.push((i + NUM_DIGITS, guess_char, Scalar::from(1u8).to_bytes()));
-        C.push((i + NUM_DIGITS, i + NUM_DIGITS, Scalar::from(outputs.letter_correct[i] as u8).to_bytes()));
+fn parse_number(value: &str) -> Result<usize, String> {
+    value
+        .parse()
+        .map_err(|_| format!("expected a number, not {}\n{}", value, usage()))
+}
 
-        // Replace Scalar::zero() with Scalar::from(0u8)
-        let mut vars = vec![Scalar::from(0u8).to_bytes(); num_vars];
-        for i in 0..NUM_DIGITS {
-            // Replace Scalar::one() with Scalar::from(1u8)
-            vars[inputs.hidden_word[i] as usize] = Scalar::from(1u8).to_bytes();
-            vars[inputs.guess[i] as usize + DIGIT_RANGE] = Scalar::from(1u8).to_bytes();
+fn parse_args() -> Result<Options, String> {
+    let mut backend = DEFAULT_BACKEND.to_string();
+    let mut check_witness = false;
+    let mut dictionary = None;
+    let mut guesses = None;
+    let mut verifying_key = None;
+    let mut config = GameConfig::default();
+    let mut positional = Vec::new();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--backend" => backend = args.next().ok_or_else(usage)?,
+            "--word-length" => config.word_len = parse_number(&args.next().ok_or_else(usage)?)?,
+            "--alphabet" => {
+                let alphabet = args.next().ok_or_else(usage)?;
+                config.alphabet = alphabet.parse().map_err(|e| format!("{}\n{}", e, usage()))?;
+            }
+            "--answer-tree-depth" => config.answer_tree_depth = parse_number(&args.next().ok_or_else(usage)?)?,
+            "--max-guesses" => {
+                config.max_guesses = match args.next().ok_or_else(usage)?.as_str() {
+                    "unlimited" => None,
+                    value => Some(parse_number(value)?),
+                }
+            }
+            "--check-witness" => check_witness = true,
+            "--dictionary" => dictionary = Some(args.next().ok_or_else(usage)?),
+            "--guesses" => guesses = Some(args.next().ok_or_else(usage)?),
+            "--verifying-key" => verifying_key = Some(args.next().ok_or_else(usage)?),
+            "-h" | "--help" => return Err(usage()),
+            _ if arg.starts_with('-') => return Err(format!("unexpected option {}\n{}", arg, usage())),
+            _ => positional.push(arg),
         }
-
     }
-
-    Instance::new(num_cons, num_vars, num_inputs, &A, &B, &C).unwrap()
+    if !BACKENDS.contains(&backend.as_str()) {
+        return Err(format!("unknown backend {}\n{}", backend, usage()));
+    }
+    config.validate().map_err(|e| format!("{}\n{}", e, usage()))?;
+
+    let mut positional = positional.into_iter();
+    let name = positional.next();
+    let mut next = || positional.next().ok_or_else(|| format!("missing arguments\n{}", usage()));
+    let command = match name.as_deref() {
+        None | Some("play") => Command::Play,
+        Some("commit") => Command::Commit {
+            secret: next()?,
+            word: next().ok(),
+        },
+        Some("prove") => Command::Prove {
+            secret: next()?,
+            guess: next()?,
+            proof: next()?,
+        },
+        Some("verify") => Command::Verify {
+            commitment: next()?,
+            guess: next()?,
+            feedback: next()?,
+            proof: next()?,
+            verifying_key: verifying_key
+                .clone()
+                .ok_or_else(|| format!("verify needs --verifying-key\n{}", usage()))?,
+        },
+        Some("verify-rejection") => Command::VerifyRejection {
+            guess: next()?,
+            proof: next()?,
+        },
+        Some("reveal") => Command::Reveal {
+            commitment: next()?,
+            secret: next()?,
+        },
+        Some(other) => return Err(format!("unknown command {}\n{}", other, usage())),
+    };
+    if next().is_ok() {
+        return Err(format!("too many arguments\n{}", usage()));
+    }
+    Ok(Options {
+        backend,
+        config,
+        check_witness,
+        dictionary,
+        guesses,
+        verifying_key,
+        command,
+    })
 }
 
-// Helper function to convert Vec<u8> to [u8; 32]
-fn vec_to_array_32(vec: Vec<u8>) -> [u8; 32] {
-    let mut array = [0u8; 32];
-    array[..vec.len()].copy_from_slice(&vec);
-    array
+fn main() -> Result<(), Box<dyn Error>> {
+    let options = parse_args().unwrap_or_else(|message| {
+        eprintln!("{}", message);
+        process::exit(2);
+    });
+
+    match options.backend.as_str() {
+        #[cfg(feature = "spartan")]
+        SpartanSnark::NAME => run::<SpartanSnark>(&options),
+        #[cfg(feature = "spartan")]
+        SpartanNizk::NAME => run::<SpartanNizk>(&options),
+        #[cfg(feature = "groth16")]
+        Groth16::NAME => run::<Groth16>(&options),
+        _ => unreachable!("parse_args only accepts compiled-in backends"),
+    }
 }
 
-// Prover function
-fn prove_game(hidden_word: &[u8], guess: &[u8]) -> (Vec<u8>, Vec<bool>, Vec<bool>) {
-    let inputs = GameInputs {
-        hidden_word: hidden_word.to_vec(),
-        guess: guess.to_vec(),
-    };
-
-    let letter_in_word: Vec<bool> = inputs.hidden_word
-        .iter()
-        .map(|&h| inputs.guess.contains(&h))
-        .collect();
-
-    let letter_correct: Vec<bool> = inputs.hidden_word
-        .iter()
-        .zip(inputs.guess.iter())
-        .map(|(h, g)| h == g)
-        .collect();
-
-    let outputs = GameOutputs {
-        letter_in_word,
-        letter_correct,
-    };
-
-    let inst = game_constraints(&inputs, &outputs);
-    let num_cons = NUM_DIGITS * 2;
-    let num_vars = NUM_DIGITS * 2;
-    let num_inputs = NUM_DIGITS * 2;
-    let num_non_zero_entries = NUM_DIGITS * 2;
-
-    let gens = SNARKGens::new(num_cons, num_vars, num_inputs, num_non_zero_entries);
-
-    let (comm, decomm) = SNARK::encode(&inst, &gens);
-
-    let mut vars = vec![Scalar::from(0u8).to_bytes(); num_vars];
-    for i in 0..NUM_DIGITS {
-        // Replace Scalar::one() with Scalar::from(1u8)
-        vars[inputs.hidden_word[i] as usize] = Scalar::from(1u8).to_bytes();
-        vars[inputs.guess[i] as usize + DIGIT_RANGE] = Scalar::from(1u8).to_bytes();
+fn run<S: ProofSystem>(options: &Options) -> Result<(), Box<dyn Error>> {
+    let config = &options.config;
+    match &options.command {
+        Command::Play => play::<S>(config, &load_dictionary(options)?, options.check_witness),
+        Command::Commit { secret, word } => {
+            let verifying_key = match &options.verifying_key {
+                Some(path) => PathBuf::from(path),
+                None => S::params_path(config).with_extension("vk"),
+            };
+            commit::<S>(config, secret, word.as_deref(), &verifying_key, &load_dictionary(options)?)
+        }
+        Command::Prove { secret, guess, proof } => {
+            prove::<S>(config, secret, guess, proof, &load_dictionary(options)?, options.check_witness)
+        }
+        Command::Verify {
+            commitment,
+            guess,
+            feedback,
+            proof,
+            verifying_key,
+        } => verify::<S>(config, commitment, guess, feedback, proof, verifying_key, &load_dictionary(options)?),
+        Command::VerifyRejection { guess, proof } => {
+            verify_rejection::<S>(config, guess, proof, &load_dictionary(options)?)
+        }
+        Command::Reveal { commitment, secret } => reveal::<S>(config, commitment, secret),
     }
+}
 
-    let assignment_vars = VarsAssignment::new(&vars).unwrap();
-    let assignment_inputs = InputsAssignment::new(&[vec_to_array_32(inputs.hidden_word.clone())]).unwrap();
-
-
-    let mut prover_transcript = Transcript::new(b"zk_wordle");
-    let proof = SNARK::prove(
-        &inst,
-        &comm,
-        &decomm,
-        assignment_vars,
-        &assignment_inputs,
-        &gens,
-        &mut prover_transcript,
-    );
+// The word lists given on the command line, falling back to the built-in ones
+fn load_dictionary(options: &Options) -> Result<Dictionary, ZkWordleError> {
+    let config = &options.config;
+    match (&options.dictionary, &options.guesses) {
+        (None, None) => Dictionary::embedded(config),
+        (Some(answers), guesses) => Dictionary::load(config, answers, guesses.as_ref()),
+        (None, Some(guesses)) => Dictionary::new(
+            config,
+            Dictionary::embedded(config)?.answers().to_vec(),
+            load_words(config, guesses)?,
+        ),
+    }
+}
 
-    let proof_bytes = bincode::serialize(&proof).unwrap();
-    (proof_bytes, outputs.letter_in_word, outputs.letter_correct)
+fn random_word(dictionary: &Dictionary) -> Result<String, ZkWordleError> {
+    dictionary
+        .answers()
+        .choose(&mut thread_rng())
+        .cloned()
+        .ok_or(ZkWordleError::InvalidWord("no words found".to_string()))
 }
 
-// Verifier function
-fn verify_game(hidden_word: &[u8], guess: &[u8], proof_bytes: &[u8]) -> bool {
-    let inputs = GameInputs {
-        hidden_word: hidden_word.to_vec(),
-        guess: guess.to_vec(),
-    };
+fn read_secret(path: &str) -> Result<GameSecret, Box<dyn Error>> {
+    Ok(fs::read_to_string(path)?.parse()?)
+}
 
-    let outputs = GameOutputs {
-        letter_in_word: vec![false; NUM_DIGITS],
-        letter_correct: vec![false; NUM_DIGITS],
+// Host: starts a game. The secret file stays with the host until the reveal,
+// while the verifying key goes to the players.
+fn commit<S: ProofSystem>(
+    config: &GameConfig,
+    secret_path: &str,
+    word: Option<&str>,
+    verifying_key_path: &Path,
+    dictionary: &Dictionary,
+) -> Result<(), Box<dyn Error>> {
+    let word = match word {
+        Some(word) => word.to_string(),
+        None => random_word(dictionary)?,
     };
+    let hidden_word = Guess::parse(config, &word)
+        .map_err(|_| ZkWordleError::InvalidWord(format!("'{}' is not a word of the game", word)))?;
+    if !dictionary.answers().contains(&hidden_word.to_string()) {
+        return Err(ZkWordleError::InvalidWord(format!("'{}' is not one of the answers", word)).into());
+    }
+    let hidden_word = hidden_word.letters().to_vec();
+    let params = S::load_or_setup(config, S::params_path(config))?;
+    S::save_verifying_key(&S::verifying_key(&params), verifying_key_path)?;
+    let secret = GameSecret::generate::<S::Field>(config, hidden_word)?;
+    fs::write(secret_path, format!("{}\n", secret))?;
+    println!("{}", secret.commitment::<S::Field>(config)?);
+    Ok(())
+}
 
-    let inst = game_constraints(&inputs, &outputs);
-    let num_cons = NUM_DIGITS * 2;
-    let num_vars = NUM_DIGITS * 2;
-    let num_inputs = NUM_DIGITS * 2;
-    let num_non_zero_entries = NUM_DIGITS * 2;
-
-    let gens = SNARKGens::new(num_cons, num_vars, num_inputs, num_non_zero_entries);
-
-    let (comm, _) = SNARK::encode(&inst, &gens);
-
-    let proof: SNARK = bincode::deserialize(proof_bytes).unwrap();
-
-    let assignment_inputs = InputsAssignment::new(&[vec_to_array_32(inputs.hidden_word.clone())]).unwrap();
+// Host: answers one guess with its feedback and a proof file for the player
+fn prove<S: ProofSystem>(
+    config: &GameConfig,
+    secret_path: &str,
+    guess: &str,
+    proof_path: &str,
+    dictionary: &Dictionary,
+    check_witness: bool,
+) -> Result<(), Box<dyn Error>> {
+    let secret = read_secret(secret_path)?;
+    let guess = Guess::parse(config, guess)?;
+    if let Some(absence) = dictionary.allowed_tree::<S::Field>()?.absence_proof(guess.letters()) {
+        fs::write(proof_path, absence.to_bytes()?)?;
+        println!("'{}' is not in the word list", guess);
+        process::exit(1)
+    }
+    // never a setup of its own, which no verifying key handed out would match
+    let params_path = S::params_path(config);
+    if !params_path.exists() {
+        return Err(format!("no parameters at {}; commit creates them", params_path.display()).into());
+    }
+    let params = S::load_params(config, params_path)?;
+    let prover = Prover::<S>::from_secret(config, &dictionary.answer_tree()?, &secret, Arc::new(params))?;
+    if check_witness {
+        prover.check_witness(&guess)?;
+    }
 
-    let mut verifier_transcript = Transcript::new(b"zk_wordle");
-    proof
-        .verify(&comm, &assignment_inputs, &mut verifier_transcript, &gens)
-        .is_ok()
+    let (feedback, proof) = prover.prove(&guess)?;
+    fs::write(proof_path, proof.as_bytes())?;
+    println!("{}", feedback);
+    Ok(())
 }
 
-fn main() {
-    let file = File::open("/usr/share/dict/words").expect("Failed to open file");
-    let lines = io::BufReader::new(file).lines();
-    let five_letter_words: Vec<String> = lines
-        .filter_map(Result::ok)
-        .filter(|line| line.len() == 5 && line.chars().all(|c| c.is_ascii_alphabetic()))
-        .collect();
+// Player: checks the host's answer to a guess against the commitment
+fn verify<S: ProofSystem>(
+    config: &GameConfig,
+    commitment: &str,
+    guess: &str,
+    feedback: &str,
+    proof_path: &str,
+    verifying_key_path: &str,
+    dictionary: &Dictionary,
+) -> Result<(), Box<dyn Error>> {
+    let commitment: Commitment = commitment.parse()?;
+    let guess = Guess::parse(config, guess)?;
+    let feedback: Feedback = feedback.parse()?;
+    let proof = GuessProof::from_bytes(fs::read(proof_path)?);
+
+    let key = Arc::new(S::load_verifying_key(config, verifying_key_path)?);
+    let answers_root = dictionary.answer_tree::<S::Field>()?.root();
+    let verifier = Verifier::<S>::new(config, commitment, answers_root, key);
+    match verifier.verify(&guess, &feedback, &proof) {
+        Ok(true) => {
+            println!("valid");
+            Ok(())
+        }
+        Ok(false) => {
+            println!("invalid");
+            process::exit(1)
+        }
+        Err(err) => {
+            println!("invalid: {}", err);
+            process::exit(1)
+        }
+    }
+}
 
-    let random_word = five_letter_words
-        .choose(&mut thread_rng())
-        .expect("No words found")
-        .to_string();
+// Player: checks the host's proof that a guess it turned down is not a word
+fn verify_rejection<S: ProofSystem>(
+    config: &GameConfig,
+    guess: &str,
+    proof_path: &str,
+    dictionary: &Dictionary,
+) -> Result<(), Box<dyn Error>> {
+    let guess = Guess::parse(config, guess)?;
+    let allowed_root = dictionary.allowed_tree::<S::Field>()?.root();
+    let verified = AbsenceProof::<S::Field>::from_bytes(&fs::read(proof_path)?)
+        .and_then(|proof| proof.verify(config, &allowed_root, &guess));
+    match verified {
+        Ok(true) => {
+            println!("valid");
+            Ok(())
+        }
+        Ok(false) => {
+            println!("invalid");
+            process::exit(1)
+        }
+        Err(err) => {
+            println!("invalid: {}", err);
+            process::exit(1)
+        }
+    }
+}
 
-    let hidden_word: Vec<u8> = random_word.chars().map(|c| c as u8 - b'a').collect();
+// Player: checks the secret disclosed at the end of the game
+fn reveal<S: ProofSystem>(config: &GameConfig, commitment: &str, secret_path: &str) -> Result<(), Box<dyn Error>> {
+    let commitment: Commitment = commitment.parse()?;
+    let secret = read_secret(secret_path)?;
+    if !secret.opens::<S::Field>(config, &commitment) {
+        println!("the secret does not open the commitment");
+        process::exit(1)
+    }
+    println!("The word was {}", secret.word());
+    Ok(())
+}
 
-    println!("Welcome to Wordle! You have 6 guesses to guess the word.");
-    println!("The word is a 5-letter word that contains only alphabetic characters.");
+fn play<S: ProofSystem>(config: &GameConfig, dictionary: &Dictionary, check_witness: bool) -> Result<(), Box<dyn Error>> {
+    let random_word = random_word(dictionary)?;
+
+    let hidden_word = config.alphabet.split(&random_word).map_err(ZkWordleError::InvalidWord)?;
+
+    // built once; every turn reuses the same setup
+    let start = Instant::now();
+    let params_path = S::params_path(config);
+    let params = Arc::new(S::load_or_setup(config, &params_path)?);
+    let setup_time = start.elapsed();
+    let answers = dictionary.answer_tree::<S::Field>()?;
+    let allowed = dictionary.allowed_tree::<S::Field>()?;
+    let prover = Prover::<S>::new(config, &answers, hidden_word, params.clone())?;
+    // the player's side, which holds every turn to the commitment printed below
+    let mut player = GameVerifier::<S>::new(config, prover.commitment(), answers.root(), S::verifying_key(&params));
+
+    match config.max_guesses {
+        Some(max_guesses) => println!("Welcome to Wordle! You have {} guesses to guess the word.", max_guesses),
+        None => println!("Welcome to Wordle! This is a practice game: guess for as long as you like."),
+    }
+    println!(
+        "The word has {} letters, each one of {}.",
+        config.word_len, config.alphabet
+    );
     println!("This game will also generate zero-knowledge proofs that you can verify to prove that this program is not cheating.");
-
-    for turn in 0..6 {
-        println!("{:?}: Enter your guess: ", turn + 1);
-        let guess = {
+    println!("Commitment to the hidden word: {}", prover.commitment());
+    println!("Root of the {} possible answers: {}", dictionary.answers().len(), answers.root());
+    println!("Root of the {} allowed guesses: {}", dictionary.allowed().len(), allowed.root());
+    println!("Circuit parameters: {} ({} backend)", params_path.display(), S::NAME);
+    println!("Setup took {:.2?}", setup_time);
+
+    let result = 'game: loop {
+        // invalid input re-prompts without using up the turn
+        let guess = loop {
+            println!("{:?}: Enter your guess: ", player.game().guesses() + 1);
             let mut input = String::new();
-            io::stdin()
-                .read_line(&mut input)
-                .expect("Failed to read line");
-            input
+            if io::stdin().read_line(&mut input)? == 0 {
+                break 'game player.give_up();
+            }
+
+            let guess = match Guess::parse(config, &input) {
+                Ok(guess) => guess,
+                Err(err) => {
+                    println!("{}", err);
+                    continue;
+                }
+            };
+            // a guess that is not a word is turned down with a proof of that
+            match allowed.absence_proof(guess.letters()) {
+                None => break guess,
+                Some(absence) => println!(
+                    "'{}' is not in the word list (proof verification result: {})",
+                    guess,
+                    absence.verify(config, &allowed.root(), &guess)?
+                ),
+            }
         };
+        if check_witness {
+            prover.check_witness(&guess)?;
+            println!("Witness satisfies every constraint");
+        }
 
-        let guess_word: Vec<u8> = guess.trim().chars().map(|c| c as u8 - b'a').collect();
-        let (proof_bytes, letter_in_word, letter_correct) = prove_game(&hidden_word, &guess_word);
+        let start = Instant::now();
+        let (feedback, proof) = prover.prove(&guess)?;
+        let prove_time = start.elapsed();
 
-        println!("Letter in word: {:?}", letter_in_word);
-        println!("Letter correct: {:?}", letter_correct);
+        println!("Feedback: {}", feedback);
 
-        let verified = verify_game(&hidden_word, &guess_word, &proof_bytes);
+        let start = Instant::now();
+        let verified = player.verify_turn(&prover.commitment(), &guess, &feedback, &proof)?;
+        let verify_time = start.elapsed();
         println!("Verification result: {}", verified);
-
-        if letter_correct.iter().all(|&b| b) {
-            println!("Congrats! You guessed the wordle!");
-            break;
+        println!(
+            "Proof: {} bytes, proved in {:.2?}, verified in {:.2?}",
+            proof.as_bytes().len(),
+            prove_time,
+            verify_time
+        );
+
+        if !verified {
+            return Err("the host's proof did not verify, so the turn does not count".into());
         }
+        if let Some(result) = player.game().result() {
+            break result;
+        }
+    };
+
+    match result {
+        GameResult::Won { guesses } => println!("Congrats! You guessed the wordle on guess {}!", guesses),
+        GameResult::Lost { guesses } => println!("Game over after {} guesses.", guesses),
     }
 
-    println!("The word was {}", random_word);
-}
\ No newline at end of file
+    // the word, with a proof that it is the one committed to at the start
+    let (word, proof) = prover.reveal()?;
+    println!("The word was {}", word);
+    println!("Reveal verification result: {}", player.verify_reveal(&word, &proof)?);
+    Ok(())
+}
@@ -0,0 +1,398 @@
+// Merkle trees over word lists, hashed with MiMC in the proof field.
+//
+// The leaves are the packed words (commitment::pack_word) in the order of
+// their letters, so a list gives the same root however it was read in, padded
+// up to 2^depth leaves with alphabet_size^word_len. That is one past the
+// largest packed word, so a padding leaf never opens to a word. Every node is
+// the compression E_left(right) + left + right of its children.
+//
+// Being sorted, a tree also proves that a word is missing from it: the leaves
+// on either side of where the word would go are adjacent, and it falls
+// strictly between them.
+
+use bincode::Options;
+use ff::PrimeField;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::commitment::{pack_word, parse_hex, write_hex};
+use crate::config::GameConfig;
+use crate::error::ZkWordleError;
+use crate::guess::{check_letters, Guess};
+use crate::mimc;
+
+// Absence proofs arrive from the untrusted host; honest ones hold two paths
+// of at most a few dozen levels
+const MAX_ABSENCE_PROOF_BYTES: u64 = 1 << 12;
+
+// Fills the tree past the last word
+fn padding_leaf<F: PrimeField>(config: &GameConfig) -> F {
+    F::from(config.alphabet.size() as u64).pow_vartime([config.word_len as u64])
+}
+
+// Root of a word tree, as the canonical little-endian encoding of a field
+// element of the proof backend. Published with the word list it commits to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MerkleRoot([u8; 32]);
+
+impl MerkleRoot {
+    // Unchecked: each backend verifies the bytes encode an element of its field
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        MerkleRoot(bytes)
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+
+    pub(crate) fn field_element<F: PrimeField<Repr = [u8; 32]>>(&self) -> Result<F, ZkWordleError> {
+        Option::from(F::from_repr(self.0)).ok_or(ZkWordleError::InvalidRoot)
+    }
+}
+
+impl fmt::Display for MerkleRoot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_hex(f, &self.0)
+    }
+}
+
+impl FromStr for MerkleRoot {
+    type Err = ZkWordleError;
+
+    fn from_str(s: &str) -> Result<Self, ZkWordleError> {
+        parse_hex(s.trim()).map(MerkleRoot).ok_or(ZkWordleError::InvalidRoot)
+    }
+}
+
+// Siblings from a leaf up to the root. Bit i of the leaf's index tells whether
+// the node at level i is a right child.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerklePath<F: PrimeField> {
+    index: usize,
+    siblings: Vec<F>,
+}
+
+impl<F: PrimeField> MerklePath<F> {
+    pub fn depth(&self) -> usize {
+        self.siblings.len()
+    }
+
+    pub(crate) fn is_right(&self, level: usize) -> bool {
+        (self.index >> level) & 1 == 1
+    }
+
+    pub(crate) fn siblings(&self) -> &[F] {
+        &self.siblings
+    }
+
+    // The root this path leads to from the given leaf
+    pub(crate) fn root(&self, leaf: F) -> F {
+        let constants = mimc::round_constants::<F>();
+        self.siblings.iter().enumerate().fold(leaf, |node, (level, &sibling)| {
+            if self.is_right(level) {
+                mimc::compress(sibling, node, &constants)
+            } else {
+                mimc::compress(node, sibling, &constants)
+            }
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct WordTree<F: PrimeField> {
+    // sorted letter indices, one per leaf
+    words: Vec<Vec<u8>>,
+    // levels[0] holds the leaves and levels[depth] the root, each level
+    // without the nodes that cover only padding
+    levels: Vec<Vec<F>>,
+    // the node over padding alone, at every level
+    padding: Vec<F>,
+}
+
+impl<F: PrimeField<Repr = [u8; 32]>> WordTree<F> {
+    pub fn new(config: &GameConfig, depth: usize, words: &[Vec<u8>]) -> Result<Self, ZkWordleError> {
+        let mut words = words.to_vec();
+        for word in words.iter() {
+            check_letters(config, word).map_err(ZkWordleError::InvalidWord)?;
+        }
+        words.sort();
+        words.dedup();
+        if depth >= usize::BITS as usize || words.len() > 1 << depth {
+            return Err(ZkWordleError::InvalidConfig(format!(
+                "{} words do not fit in a tree of depth {}",
+                words.len(),
+                depth
+            )));
+        }
+
+        let constants = mimc::round_constants::<F>();
+        let mut padding = vec![padding_leaf(config)];
+        let mut levels = vec![words.iter().map(|word| pack_word::<F>(config, word)).collect::<Vec<F>>()];
+        for level in 0..depth {
+            let empty = padding[level];
+            let parents = levels[level]
+                .chunks(2)
+                .map(|pair| mimc::compress(pair[0], pair.get(1).copied().unwrap_or(empty), &constants))
+                .collect();
+            levels.push(parents);
+            padding.push(mimc::compress(empty, empty, &constants));
+        }
+        Ok(WordTree { words, levels, padding })
+    }
+
+    pub fn depth(&self) -> usize {
+        self.levels.len() - 1
+    }
+
+    pub fn words(&self) -> &[Vec<u8>] {
+        &self.words
+    }
+
+    pub fn root(&self) -> MerkleRoot {
+        MerkleRoot(self.root_element().to_repr())
+    }
+
+    pub(crate) fn root_element(&self) -> F {
+        self.levels[self.depth()].first().copied().unwrap_or(self.padding[self.depth()])
+    }
+
+    // None unless the word is in the tree
+    pub fn path(&self, word: &[u8]) -> Option<MerklePath<F>> {
+        let index = self.words.binary_search_by(|w| w.as_slice().cmp(word)).ok()?;
+        Some(self.path_at(index))
+    }
+
+    fn path_at(&self, index: usize) -> MerklePath<F> {
+        let siblings = (0..self.depth())
+            .map(|level| {
+                let sibling = (index >> level) ^ 1;
+                self.levels[level].get(sibling).copied().unwrap_or(self.padding[level])
+            })
+            .collect();
+        MerklePath { index, siblings }
+    }
+
+    // The leaf at index, with None for padding
+    fn neighbour(&self, index: usize) -> Option<Neighbour<F>> {
+        (index < 1 << self.depth()).then(|| Neighbour {
+            word: self.words.get(index).cloned(),
+            path: self.path_at(index),
+        })
+    }
+
+    // None if the word is in the tree after all
+    pub fn absence_proof(&self, word: &[u8]) -> Option<AbsenceProof<F>> {
+        let index = match self.words.binary_search_by(|w| w.as_slice().cmp(word)) {
+            Ok(_) => return None,
+            Err(index) => index,
+        };
+        Some(AbsenceProof {
+            below: index.checked_sub(1).and_then(|below| self.neighbour(below)),
+            above: self.neighbour(index),
+        })
+    }
+}
+
+// A leaf of the tree, with its path: a word, or padding once the words run out
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Neighbour<F: PrimeField> {
+    word: Option<Vec<u8>>,
+    path: MerklePath<F>,
+}
+
+// The leaves right before and right after where a word would sit in a sorted
+// tree. There is none before a word that would come first, and none after one
+// that would come last in a full tree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AbsenceProof<F: PrimeField> {
+    below: Option<Neighbour<F>>,
+    above: Option<Neighbour<F>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct EncodedNeighbour {
+    word: Option<Vec<u8>>,
+    index: u64,
+    siblings: Vec<[u8; 32]>,
+}
+
+fn absence_proof_encoding() -> impl Options {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .with_limit(MAX_ABSENCE_PROOF_BYTES)
+}
+
+impl<F: PrimeField<Repr = [u8; 32]>> AbsenceProof<F> {
+    // Whether the proof shows that the guess is missing from the tree with
+    // the given root. Err if the root is not an element of F.
+    pub fn verify(&self, config: &GameConfig, root: &MerkleRoot, guess: &Guess) -> Result<bool, ZkWordleError> {
+        let root = root.field_element::<F>()?;
+        let word = guess.letters();
+        if check_letters(config, word).is_err() {
+            return Ok(false);
+        }
+
+        // a leaf of the tree, holding a word of the game or else padding.
+        // Letters outside the alphabet could pack to the leaf of another word.
+        let is_leaf = |neighbour: &Neighbour<F>| {
+            let leaf = match &neighbour.word {
+                Some(word) if check_letters(config, word).is_err() => return false,
+                Some(word) => pack_word(config, word),
+                None => padding_leaf(config),
+            };
+            let path = &neighbour.path;
+            path.depth() < usize::BITS as usize && path.index >> path.depth() == 0 && path.root(leaf) == root
+        };
+        let last_index = |neighbour: &Neighbour<F>| (1usize << neighbour.path.depth()) - 1;
+
+        Ok(match (&self.below, &self.above) {
+            (Some(below), Some(above)) => {
+                below.word.as_deref().is_some_and(|w| w < word)
+                    && above.word.as_deref().is_none_or(|w| w > word)
+                    && is_leaf(below)
+                    && is_leaf(above)
+                    && below.path.depth() == above.path.depth()
+                    && above.path.index == below.path.index + 1
+            }
+            (None, Some(first)) => {
+                first.word.as_deref().is_none_or(|w| w > word) && is_leaf(first) && first.path.index == 0
+            }
+            (Some(last), None) => {
+                last.word.as_deref().is_some_and(|w| w < word)
+                    && is_leaf(last)
+                    && last.path.index == last_index(last)
+            }
+            (None, None) => false,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ZkWordleError> {
+        let encode = |neighbour: &Option<Neighbour<F>>| {
+            neighbour.as_ref().map(|n| EncodedNeighbour {
+                word: n.word.clone(),
+                index: n.path.index as u64,
+                siblings: n.path.siblings.iter().map(|s| s.to_repr()).collect(),
+            })
+        };
+        absence_proof_encoding()
+            .serialize(&(encode(&self.below), encode(&self.above)))
+            .map_err(ZkWordleError::Serialization)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ZkWordleError> {
+        let malformed = |reason: String| ZkWordleError::MalformedProof(reason);
+        let (below, above): (Option<EncodedNeighbour>, Option<EncodedNeighbour>) = absence_proof_encoding()
+            .deserialize(bytes)
+            .map_err(|e| malformed(e.to_string()))?;
+        let decode = |neighbour: Option<EncodedNeighbour>| -> Result<Option<Neighbour<F>>, ZkWordleError> {
+            let Some(neighbour) = neighbour else {
+                return Ok(None);
+            };
+            let siblings = neighbour
+                .siblings
+                .iter()
+                .map(|s| {
+                    Option::from(F::from_repr(*s)).ok_or_else(|| malformed("sibling is not a field element".to_string()))
+                })
+                .collect::<Result<_, _>>()?;
+            let index = usize::try_from(neighbour.index).map_err(|e| malformed(e.to_string()))?;
+            Ok(Some(Neighbour {
+                word: neighbour.word,
+                path: MerklePath { index, siblings },
+            }))
+        };
+        Ok(AbsenceProof {
+            below: decode(below)?,
+            above: decode(above)?,
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::alphabet::Alphabet;
+    use curve25519_dalek::scalar::Scalar;
+
+    // Tree of the config's depth over the given words
+    pub(crate) fn answer_tree<F: PrimeField<Repr = [u8; 32]>>(config: &GameConfig, words: &[&str]) -> WordTree<F> {
+        let words: Vec<Vec<u8>> = words.iter().map(|w| config.alphabet.split(w).unwrap()).collect();
+        WordTree::new(config, config.answer_tree_depth, &words).unwrap()
+    }
+
+    #[test]
+    fn paths_lead_to_the_root() {
+        let config = GameConfig::default();
+        let tree = answer_tree::<Scalar>(&config, &["slate", "crane", "robot", "crane", "floor"]);
+        assert_eq!(tree.words().len(), 4);
+        let root = tree.root().field_element::<Scalar>().unwrap();
+        for word in ["crane", "floor", "robot", "slate"] {
+            let word = config.alphabet.split(word).unwrap();
+            let path = tree.path(&word).unwrap();
+            assert_eq!(path.depth(), config.answer_tree_depth);
+            assert_eq!(path.root(pack_word(&config, &word)), root);
+            assert_ne!(path.root(pack_word(&config, &config.alphabet.split("zzzzz").unwrap())), root);
+        }
+        assert!(tree.path(&config.alphabet.split("zzzzz").unwrap()).is_none());
+
+        // the root depends on the words, not the order they came in
+        assert_eq!(answer_tree::<Scalar>(&config, &["floor", "robot", "crane", "slate"]).root(), tree.root());
+        assert_ne!(answer_tree::<Scalar>(&config, &["floor", "robot", "crane"]).root(), tree.root());
+        assert_eq!(tree.root().to_string().parse::<MerkleRoot>().unwrap(), tree.root());
+
+        // and fits only as many words as the tree has leaves
+        let abc = GameConfig::new(2, "abc".parse::<Alphabet>().unwrap(), Some(6)).unwrap();
+        let every_word: Vec<Vec<u8>> = (0..9).map(|i| vec![i / 3, i % 3]).collect();
+        assert!(WordTree::<Scalar>::new(&abc, 4, &every_word).is_ok());
+        assert!(WordTree::<Scalar>::new(&abc, 3, &every_word).is_err());
+        assert!(WordTree::<Scalar>::new(&abc, 4, &[vec![0, 3]]).is_err());
+    }
+
+    #[test]
+    fn absence_proofs_show_where_a_word_would_be() {
+        let config = GameConfig::default();
+        let guess = |word: &str| Guess::parse(&config, word).unwrap();
+        let tree = answer_tree::<Scalar>(&config, &["crane", "floor", "robot", "slate"]);
+        let full = WordTree::<Scalar>::new(&config, 2, tree.words()).unwrap();
+        let root = tree.root();
+
+        // before the first word, between two, after the last
+        for (tree, word) in [(&tree, "abbey"), (&tree, "eerie"), (&tree, "zesty"), (&full, "zesty")] {
+            let proof = tree.absence_proof(guess(word).letters()).unwrap();
+            assert!(proof.verify(&config, &tree.root(), &guess(word)).unwrap(), "{}", word);
+            let decoded = AbsenceProof::<Scalar>::from_bytes(&proof.to_bytes().unwrap()).unwrap();
+            assert_eq!(decoded, proof);
+        }
+        assert!(tree.absence_proof(guess("robot").letters()).is_none());
+
+        // a proof for one word says nothing about others, words in the tree
+        // included, nor about another tree
+        let proof = tree.absence_proof(guess("eerie").letters()).unwrap();
+        for word in ["crane", "floor", "abbey", "robot"] {
+            assert!(!proof.verify(&config, &root, &guess(word)).unwrap(), "{}", word);
+        }
+        let other = answer_tree::<Scalar>(&config, &["crane", "eerie", "floor"]).root();
+        assert!(!proof.verify(&config, &other, &guess("eerie")).unwrap());
+
+        // nor do leaves that are not adjacent, though each is in the tree
+        let skipping = AbsenceProof {
+            below: tree.neighbour(0),
+            above: tree.neighbour(2),
+        };
+        assert!(!skipping.verify(&config, &root, &guess("floor")).unwrap());
+        let first_missing = AbsenceProof {
+            below: None,
+            above: tree.neighbour(1),
+        };
+        assert!(!first_missing.verify(&config, &root, &guess("eerie")).unwrap());
+        let padding_before_the_end = AbsenceProof {
+            below: tree.neighbour(2),
+            above: tree.neighbour(4),
+        };
+        assert!(!padding_before_the_end.verify(&config, &root, &guess("slate")).unwrap());
+
+        assert!(AbsenceProof::<Scalar>::from_bytes(&[0xff; 8]).is_err());
+        assert!(AbsenceProof::<Scalar>::from_bytes(&u64::MAX.to_le_bytes().repeat(4)).is_err());
+    }
+}
@@ -0,0 +1,71 @@
+// MiMC-5 block cipher and Miyaguchi-Preneel hash, generic over the proof field.
+//
+// x^5 is a permutation of both the Ristretto scalar field used by Spartan and
+// the BLS12-381 scalar field used by Groth16 (gcd(5, p - 1) = 1 for each), so
+// 110 rounds gives the usual ceil(log_5(p)) security margin in either. Round
+// constants are squeezed out of a fixed merlin transcript so prover and
+// verifier always agree on them.
+
+use ff::PrimeField;
+use merlin::Transcript;
+
+pub const MIMC_ROUNDS: usize = 110;
+
+// Reduces 64 uniform bytes (little-endian) modulo the field order
+pub fn from_uniform_bytes<F: PrimeField>(bytes: &[u8; 64]) -> F {
+    let limb_base = F::from(u64::MAX) + F::ONE;
+    bytes.chunks(8).rev().fold(F::ZERO, |acc, limb| {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(limb);
+        acc * limb_base + F::from(u64::from_le_bytes(buf))
+    })
+}
+
+pub fn round_constants<F: PrimeField>() -> Vec<F> {
+    let mut transcript = Transcript::new(b"zk_wordle_mimc");
+    (0..MIMC_ROUNDS)
+        .map(|_| {
+            let mut buf = [0u8; 64];
+            transcript.challenge_bytes(b"round_constant", &mut buf);
+            from_uniform_bytes(&buf)
+        })
+        .collect()
+}
+
+// E_k(x): x <- (x + k + c_r)^5 for every round, then add the key once more
+pub fn encrypt<F: PrimeField>(key: F, x: F, constants: &[F]) -> F {
+    let mut x = x;
+    for c in constants {
+        let t = x + key + c;
+        let t2 = t.square();
+        let t4 = t2.square();
+        x = t4 * t;
+    }
+    x + key
+}
+
+// Miyaguchi-Preneel compression E_h(m) + h + m
+pub fn compress<F: PrimeField>(h: F, m: F, constants: &[F]) -> F {
+    encrypt(h, m, constants) + h + m
+}
+
+// H_i = E_{H_{i-1}}(m_i) + H_{i-1} + m_i, starting from H_0 = 0
+pub fn hash<F: PrimeField>(inputs: &[F]) -> F {
+    let constants = round_constants::<F>();
+    inputs.iter().fold(F::ZERO, |h, m| compress(h, *m, &constants))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curve25519_dalek::scalar::Scalar;
+
+    #[test]
+    fn uniform_bytes_match_dalek_wide_reduction() {
+        let mut bytes = [0u8; 64];
+        for (i, b) in bytes.iter_mut().enumerate() {
+            *b = (i as u8).wrapping_mul(37).wrapping_add(11);
+        }
+        assert_eq!(from_uniform_bytes::<Scalar>(&bytes), Scalar::from_bytes_mod_order_wide(&bytes));
+    }
+}
@@ -0,0 +1,237 @@
+// On-disk public parameters.
+//
+// libspartan does not serialize SNARKGens or the circuit commitment, but both
+// are deterministic functions of the R1CS matrices and their shape. The file
+// therefore pins the matrices themselves behind a header recording the circuit
+// shape and a SHA-256 digest, so a verifier can refuse parameters that differ
+// from the circuit it was built with and third parties can compare digests.
+//
+// Loading a file does not make setup any cheaper: the keys still derive the
+// generators and encode the circuit from the matrices, which is most of the
+// work of setup. What the file adds is the digest.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::config::GameConfig;
+use crate::error::ZkWordleError;
+use crate::keys::{NizkKey, ProvingKey, VerifyingKey};
+use crate::mimc::MIMC_ROUNDS;
+use crate::r1cs::{game_constraints, GameR1cs};
+
+const MAGIC: [u8; 8] = *b"zkwordle";
+const FORMAT_VERSION: u32 = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircuitShape {
+    pub word_len: usize,
+    pub alphabet_size: usize,
+    // Alphabet::id; alphabets of one size share the circuit but not the game
+    pub alphabet: [u8; 32],
+    pub answer_tree_depth: usize,
+    pub mimc_rounds: usize,
+    pub num_cons: usize,
+    pub num_vars: usize,
+    pub num_inputs: usize,
+    pub num_non_zero_entries: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Header {
+    magic: [u8; 8],
+    version: u32,
+    shape: CircuitShape,
+    digest: [u8; 32],
+}
+
+pub struct PublicParams {
+    config: GameConfig,
+    shape: CircuitShape,
+    r1cs: GameR1cs,
+    digest: [u8; 32],
+}
+
+// Where the CLI keeps the parameters of each variant, e.g. params/wordle-5x26.bin
+pub fn params_path(config: &GameConfig) -> PathBuf {
+    PathBuf::from(format!("params/wordle-{}.bin", config.shape_name()))
+}
+
+fn shape_of(config: &GameConfig, r1cs: &GameR1cs) -> CircuitShape {
+    CircuitShape {
+        word_len: config.word_len,
+        alphabet_size: config.alphabet.size(),
+        alphabet: config.alphabet.id(),
+        answer_tree_depth: config.answer_tree_depth,
+        mimc_rounds: MIMC_ROUNDS,
+        num_cons: r1cs.num_cons,
+        num_vars: r1cs.num_vars,
+        num_inputs: r1cs.num_inputs,
+        num_non_zero_entries: r1cs.num_non_zero_entries(),
+    }
+}
+
+fn digest_of(shape: &CircuitShape, r1cs: &GameR1cs) -> Result<[u8; 32], ZkWordleError> {
+    let mut hasher = Sha256::new();
+    hasher.update(b"zk_wordle params");
+    hasher.update(bincode::serialize(shape).map_err(ZkWordleError::Serialization)?);
+    hasher.update(bincode::serialize(r1cs).map_err(ZkWordleError::Serialization)?);
+    Ok(hasher.finalize().into())
+}
+
+impl PublicParams {
+    // Parameters for this binary's circuit for the given variant
+    pub fn generate(config: &GameConfig) -> Result<Self, ZkWordleError> {
+        config.validate()?;
+        PublicParams::from_r1cs(config, game_constraints(config)?)
+    }
+
+    pub(crate) fn from_r1cs(config: &GameConfig, r1cs: GameR1cs) -> Result<Self, ZkWordleError> {
+        let shape = shape_of(config, &r1cs);
+        let digest = digest_of(&shape, &r1cs)?;
+        Ok(PublicParams {
+            config: config.clone(),
+            shape,
+            r1cs,
+            digest,
+        })
+    }
+
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<(), ZkWordleError> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| ZkWordleError::InvalidParams(e.to_string()))?;
+        }
+        let file = fs::File::create(path).map_err(|e| ZkWordleError::InvalidParams(e.to_string()))?;
+        self.write_to(file)
+    }
+
+    pub fn write_to<W: Write>(&self, mut writer: W) -> Result<(), ZkWordleError> {
+        let header = Header {
+            magic: MAGIC,
+            version: FORMAT_VERSION,
+            shape: self.shape,
+            digest: self.digest,
+        };
+        let mut bytes = bincode::serialize(&header).map_err(ZkWordleError::Serialization)?;
+        bytes.extend(bincode::serialize(&self.r1cs).map_err(ZkWordleError::Serialization)?);
+        writer
+            .write_all(&bytes)
+            .map_err(|e| ZkWordleError::InvalidParams(e.to_string()))
+    }
+
+    pub fn read<P: AsRef<Path>>(config: &GameConfig, path: P) -> Result<Self, ZkWordleError> {
+        let file = fs::File::open(path).map_err(|e| ZkWordleError::InvalidParams(e.to_string()))?;
+        PublicParams::read_from(config, file)
+    }
+
+    // Rejects files whose header, digest or circuit differ from what this build
+    // generates for the variant
+    pub fn read_from<R: Read>(config: &GameConfig, mut reader: R) -> Result<Self, ZkWordleError> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .map_err(|e| ZkWordleError::InvalidParams(e.to_string()))?;
+        let malformed = |e: bincode::Error| ZkWordleError::InvalidParams(e.to_string());
+
+        let header: Header = bincode::deserialize(&bytes).map_err(malformed)?;
+        if header.magic != MAGIC || header.version != FORMAT_VERSION {
+            return Err(ZkWordleError::InvalidParams("unknown file format".to_string()));
+        }
+
+        let expected = PublicParams::generate(config)?;
+        if header.shape != expected.shape {
+            return Err(ZkWordleError::InvalidParams(format!(
+                "circuit shape {:?} does not match {:?}",
+                header.shape, expected.shape
+            )));
+        }
+
+        let header_len = bincode::serialized_size(&header).map_err(malformed)? as usize;
+        let r1cs: GameR1cs = bincode::deserialize(&bytes[header_len..]).map_err(malformed)?;
+        let digest = digest_of(&header.shape, &r1cs)?;
+        if digest != header.digest {
+            return Err(ZkWordleError::InvalidParams("digest does not match contents".to_string()));
+        }
+        if digest != expected.digest {
+            return Err(ZkWordleError::InvalidParams("parameters are for a different circuit".to_string()));
+        }
+
+        Ok(PublicParams {
+            config: config.clone(),
+            shape: header.shape,
+            r1cs,
+            digest,
+        })
+    }
+
+    // Reads the file at path, creating it first if it does not exist yet
+    pub fn load_or_create<P: AsRef<Path>>(config: &GameConfig, path: P) -> Result<Self, ZkWordleError> {
+        if path.as_ref().exists() {
+            return PublicParams::read(config, path);
+        }
+        let params = PublicParams::generate(config)?;
+        params.write(path)?;
+        Ok(params)
+    }
+
+    pub fn shape(&self) -> CircuitShape {
+        self.shape
+    }
+
+    pub fn digest(&self) -> [u8; 32] {
+        self.digest
+    }
+
+    pub fn proving_key(&self) -> Result<ProvingKey, ZkWordleError> {
+        ProvingKey::from_r1cs(&self.config, &self.r1cs)
+    }
+
+    pub fn verifying_key(&self) -> Result<VerifyingKey, ZkWordleError> {
+        VerifyingKey::from_r1cs(&self.config, &self.r1cs)
+    }
+
+    pub fn nizk_key(&self) -> Result<NizkKey, ZkWordleError> {
+        NizkKey::from_r1cs(&self.config, &self.r1cs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::Alphabet;
+
+    #[test]
+    fn round_trips_and_rejects_tampering() {
+        let config = GameConfig::default();
+        let path = std::env::temp_dir().join(format!("zk-wordle-params-{}.bin", std::process::id()));
+        let params = PublicParams::generate(&config).unwrap();
+        params.write(&path).unwrap();
+
+        let loaded = PublicParams::read(&config, &path).unwrap();
+        assert_eq!(loaded.shape(), params.shape());
+        assert_eq!(loaded.digest(), params.digest());
+
+        // every variant has its own circuit, and every alphabet its own parameters
+        let six = GameConfig::new(6, Alphabet::english(), Some(6)).unwrap();
+        let reversed = GameConfig::new(5, "zyxwvutsrqponmlkjihgfedcba".parse().unwrap(), Some(6)).unwrap();
+        let shallow = GameConfig {
+            answer_tree_depth: 4,
+            ..GameConfig::default()
+        };
+        for other in [six, reversed, shallow] {
+            assert!(matches!(PublicParams::read(&other, &path), Err(ZkWordleError::InvalidParams(_))));
+            assert_ne!(params_path(&other), params_path(&config));
+        }
+
+        let mut bytes = fs::read(&path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        fs::write(&path, &bytes).unwrap();
+        assert!(matches!(PublicParams::read(&config, &path), Err(ZkWordleError::InvalidParams(_))));
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,98 @@
+// Proving backends for the Wordle circuit.
+//
+// The circuit is described once in circuit.rs and the game logic once in
+// prover.rs and verifier.rs; a backend only turns the circuit into keys and
+// proofs. Each one is compiled in behind the cargo feature of the same name.
+
+use ff::PrimeField;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::circuit::WordleCircuit;
+use crate::config::GameConfig;
+use crate::error::ZkWordleError;
+
+// Setup and parameter files are per variant of the game: the config passed to
+// them fixes the circuit, and the same config has to be used for every game
+// played with the resulting keys.
+pub trait ProofSystem {
+    // Name used to pick the backend on the command line
+    const NAME: &'static str;
+
+    // Field the circuit, the salt and the commitment live in
+    type Field: PrimeField<Repr = [u8; 32]>;
+    // Output of setup, held by the host; contains everything needed to prove
+    type Params;
+    // Held by the players; enough to check proofs
+    type VerifyingKey;
+    type Proof;
+
+    // Where the CLI keeps the output of setup between runs
+    fn params_path(config: &GameConfig) -> PathBuf;
+
+    fn setup(config: &GameConfig) -> Result<Self::Params, ZkWordleError>;
+
+    fn verifying_key(params: &Self::Params) -> Arc<Self::VerifyingKey>;
+
+    fn prove(params: &Self::Params, circuit: WordleCircuit<Self::Field>) -> Result<Self::Proof, ZkWordleError>;
+
+    // Ok(false) for a well-formed proof that does not check out
+    fn verify(key: &Self::VerifyingKey, inputs: &[Self::Field], proof: &Self::Proof) -> Result<bool, ZkWordleError>;
+
+    fn write_params<W: Write>(params: &Self::Params, writer: W) -> Result<(), ZkWordleError>;
+
+    fn read_params<R: Read>(config: &GameConfig, reader: R) -> Result<Self::Params, ZkWordleError>;
+
+    fn write_verifying_key<W: Write>(key: &Self::VerifyingKey, writer: W) -> Result<(), ZkWordleError>;
+
+    fn read_verifying_key<R: Read>(config: &GameConfig, reader: R) -> Result<Self::VerifyingKey, ZkWordleError>;
+
+    fn proof_to_bytes(proof: &Self::Proof) -> Result<Vec<u8>, ZkWordleError>;
+
+    fn proof_from_bytes(bytes: &[u8]) -> Result<Self::Proof, ZkWordleError>;
+
+    // Reads the params an earlier setup wrote to path
+    fn load_params<P: AsRef<Path>>(config: &GameConfig, path: P) -> Result<Self::Params, ZkWordleError> {
+        Self::read_params(config, BufReader::new(open(path.as_ref())?))
+    }
+
+    fn load_verifying_key<P: AsRef<Path>>(config: &GameConfig, path: P) -> Result<Self::VerifyingKey, ZkWordleError> {
+        Self::read_verifying_key(config, BufReader::new(open(path.as_ref())?))
+    }
+
+    // Writes the verifying key for the players, creating its directory if needed
+    fn save_verifying_key<P: AsRef<Path>>(key: &Self::VerifyingKey, path: P) -> Result<(), ZkWordleError> {
+        let path = path.as_ref();
+        let io_error = |e: std::io::Error| ZkWordleError::InvalidParams(format!("{}: {}", path.display(), e));
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        let mut writer = BufWriter::new(File::create(path).map_err(io_error)?);
+        Self::write_verifying_key(key, &mut writer)?;
+        writer.flush().map_err(io_error)
+    }
+
+    // Reads the params at path, running setup and writing them there first if needed
+    fn load_or_setup<P: AsRef<Path>>(config: &GameConfig, path: P) -> Result<Self::Params, ZkWordleError> {
+        let path = path.as_ref();
+        let io_error = |e: std::io::Error| ZkWordleError::InvalidParams(e.to_string());
+        if path.exists() {
+            return Self::load_params(config, path);
+        }
+
+        let params = Self::setup(config)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        let mut writer = BufWriter::new(File::create(path).map_err(io_error)?);
+        Self::write_params(&params, &mut writer)?;
+        writer.flush().map_err(io_error)?;
+        Ok(params)
+    }
+}
+
+fn open(path: &Path) -> Result<File, ZkWordleError> {
+    File::open(path).map_err(|e| ZkWordleError::InvalidParams(format!("{}: {}", path.display(), e)))
+}
@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use crate::circuit::WordleCircuit;
+use crate::commitment::{Commitment, GameSecret};
+use crate::config::GameConfig;
+use crate::error::ZkWordleError;
+use crate::feedback::Feedback;
+use crate::guess::{check_letters, Guess};
+use crate::merkle::{MerklePath, WordTree};
+use crate::proof_system::ProofSystem;
+use crate::r1cs::R1csBuilder;
+
+// Serialized proof for one guess, in the encoding of the backend that made it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GuessProof(Vec<u8>);
+
+impl GuessProof {
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        GuessProof(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+// Host side of the game: knows the hidden word and the salt of its commitment,
+// and where the word sits in the answer tree
+pub struct Prover<S: ProofSystem> {
+    config: GameConfig,
+    hidden_word: Vec<u8>,
+    salt: S::Field,
+    commitment: Commitment,
+    answer_path: MerklePath<S::Field>,
+    params: Arc<S::Params>,
+}
+
+impl<S: ProofSystem> Prover<S> {
+    // Starts a new game, committing to the word under a fresh salt. The word
+    // must be one of the answers, whose tree is built for the same config as
+    // the params.
+    pub fn new(
+        config: &GameConfig,
+        answers: &WordTree<S::Field>,
+        hidden_word: Vec<u8>,
+        params: Arc<S::Params>,
+    ) -> Result<Self, ZkWordleError> {
+        Prover::from_secret(config, answers, &GameSecret::generate::<S::Field>(config, hidden_word)?, params)
+    }
+
+    // Resumes a game committed to earlier, possibly by another process
+    pub fn from_secret(
+        config: &GameConfig,
+        answers: &WordTree<S::Field>,
+        secret: &GameSecret,
+        params: Arc<S::Params>,
+    ) -> Result<Self, ZkWordleError> {
+        if answers.depth() != config.answer_tree_depth {
+            return Err(ZkWordleError::InvalidConfig(format!(
+                "the answer tree has depth {}, the game needs {}",
+                answers.depth(),
+                config.answer_tree_depth
+            )));
+        }
+        let hidden_word = secret.hidden_word(config)?;
+        let answer_path = answers
+            .path(&hidden_word)
+            .ok_or_else(|| ZkWordleError::InvalidWord(format!("'{}' is not one of the answers", secret.word())))?;
+        let salt = secret.salt()?;
+        Ok(Prover {
+            config: config.clone(),
+            commitment: Commitment::new(config, &hidden_word, &salt),
+            hidden_word,
+            salt,
+            answer_path,
+            params,
+        })
+    }
+
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    pub fn commitment(&self) -> Commitment {
+        self.commitment
+    }
+
+    pub fn secret(&self) -> GameSecret {
+        GameSecret::new(self.config.alphabet.spell(&self.hidden_word), &self.salt)
+    }
+
+    pub fn hidden_word(&self) -> &[u8] {
+        &self.hidden_word
+    }
+
+    fn circuit(&self, guess: &Guess) -> Result<(Feedback, WordleCircuit<S::Field>), ZkWordleError> {
+        // the guess may have been parsed for another game
+        check_letters(&self.config, guess.letters()).map_err(ZkWordleError::InvalidGuess)?;
+        let feedback = Feedback::score(&self.hidden_word, guess.letters());
+        let circuit = WordleCircuit::for_turn(
+            &self.config,
+            &self.hidden_word,
+            self.salt,
+            self.commitment.field_element()?,
+            &self.answer_path,
+            guess.letters(),
+            feedback.letters(),
+        );
+        Ok((feedback, circuit))
+    }
+
+    // Debugging aid: evaluates the constraints on this turn's witness without
+    // proving, and names the first one that fails
+    pub fn check_witness(&self, guess: &Guess) -> Result<(), ZkWordleError> {
+        let (_, circuit) = self.circuit(guess)?;
+        R1csBuilder::record(circuit)?.check()
+    }
+
+    pub fn prove(&self, guess: &Guess) -> Result<(Feedback, GuessProof), ZkWordleError> {
+        let (feedback, circuit) = self.circuit(guess)?;
+        let proof = S::prove(&self.params, circuit)?;
+        Ok((feedback, GuessProof(S::proof_to_bytes(&proof)?)))
+    }
+
+    // Ends the game by disclosing the word, with a proof that it opens the
+    // commitment. That is the proof for guessing the word itself: feedback is
+    // all green only for the committed word, and the salt stays secret.
+    pub fn reveal(&self) -> Result<(Guess, GuessProof), ZkWordleError> {
+        let word = Guess::from_letters(&self.config, self.hidden_word.clone())?;
+        let (_, proof) = self.prove(&word)?;
+        Ok((word, proof))
+    }
+}
@@ -0,0 +1,412 @@
+// R1CS builder emitting libspartan instances.
+//
+// R1csBuilder is a bellman ConstraintSystem that only records: variables and
+// constraints are named by their namespace path and counted as they are
+// added, every constraint becomes one row of the A, B, C matrices in
+// libspartan's tuple format, and values are kept when the circuit carries
+// them. Spartan lays out z = (vars, 1, inputs), so auxiliary variables come
+// first, then the constant one, then the public inputs. The Wordle circuit in
+// circuit.rs is written against this interface, as can be any other gadget:
+//
+//     let mut cs = R1csBuilder::new();
+//     let x = cs.alloc(|| "x", || Ok(x_value))?;
+//     let y = cs.alloc_input(|| "y", || Ok(y_value))?;
+//     cs.enforce(|| "x squared", |lc| lc + x, |lc| lc + x, |lc| lc + y);
+//     let inst = cs.instance()?;
+//
+// The builder works over any field so the same recording doubles as a
+// backend-independent satisfiability check (check, check_assignment) for
+// debugging witnesses; only the libspartan output is tied to Spartan's field.
+
+use bellman::{Circuit, ConstraintSystem, Index, LinearCombination, SynthesisError, Variable};
+use ff::PrimeField;
+
+use crate::error::ZkWordleError;
+
+#[cfg(feature = "spartan")]
+use crate::circuit::WordleCircuit;
+#[cfg(feature = "spartan")]
+use crate::config::GameConfig;
+#[cfg(feature = "spartan")]
+use curve25519_dalek::scalar::Scalar;
+#[cfg(feature = "spartan")]
+use libspartan::{InputsAssignment, Instance, R1CSError, VarsAssignment};
+#[cfg(feature = "spartan")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "spartan")]
+pub(crate) type Matrix = Vec<(usize, usize, [u8; 32])>;
+
+// The A, B, C matrices of the game circuit, in libspartan's tuple format
+#[cfg(feature = "spartan")]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct GameR1cs {
+    pub num_cons: usize,
+    pub num_vars: usize,
+    pub num_inputs: usize,
+    pub a: Matrix,
+    pub b: Matrix,
+    pub c: Matrix,
+}
+
+#[cfg(feature = "spartan")]
+impl GameR1cs {
+    pub fn num_non_zero_entries(&self) -> usize {
+        self.a.len().max(self.b.len()).max(self.c.len())
+    }
+
+    pub fn instance(&self) -> Result<Instance, ZkWordleError> {
+        Instance::new(self.num_cons, self.num_vars, self.num_inputs, &self.a, &self.b, &self.c).map_err(circuit_error)
+    }
+}
+
+// Constraint system that writes down constraints instead of proving them.
+// Values are None when the circuit has none, as during parameter generation.
+pub struct R1csBuilder<F: PrimeField> {
+    namespace: Vec<String>,
+    vars: Vec<Option<F>>,
+    var_names: Vec<String>,
+    inputs: Vec<Option<F>>,
+    input_names: Vec<String>,
+    rows: Vec<[LinearCombination<F>; 3]>,
+    row_names: Vec<String>,
+}
+
+fn value<F, V>(f: V) -> Result<Option<F>, SynthesisError>
+where
+    V: FnOnce() -> Result<F, SynthesisError>,
+{
+    match f() {
+        Ok(value) => Ok(Some(value)),
+        Err(SynthesisError::AssignmentMissing) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+impl<F: PrimeField> ConstraintSystem<F> for R1csBuilder<F> {
+    type Root = Self;
+
+    fn alloc<V, A, AR>(&mut self, name: A, f: V) -> Result<Variable, SynthesisError>
+    where
+        V: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.vars.push(value(f)?);
+        self.var_names.push(self.path(name().into()));
+        Ok(Variable::new_unchecked(Index::Aux(self.vars.len() - 1)))
+    }
+
+    // Input(0) is bellman's constant one, so real inputs start at 1
+    fn alloc_input<V, A, AR>(&mut self, name: A, f: V) -> Result<Variable, SynthesisError>
+    where
+        V: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.inputs.push(value(f)?);
+        self.input_names.push(self.path(name().into()));
+        Ok(Variable::new_unchecked(Index::Input(self.inputs.len())))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(&mut self, name: A, a: LA, b: LB, c: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LB: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LC: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+    {
+        let zero = LinearCombination::zero;
+        self.rows.push([a(zero()), b(zero()), c(zero())]);
+        self.row_names.push(self.path(name().into()));
+    }
+
+    fn push_namespace<NR, N>(&mut self, name: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        self.namespace.push(name().into());
+    }
+
+    fn pop_namespace(&mut self) {
+        self.namespace.pop();
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+}
+
+impl<F: PrimeField> Default for R1csBuilder<F> {
+    fn default() -> Self {
+        R1csBuilder {
+            namespace: Vec::new(),
+            vars: Vec::new(),
+            var_names: Vec::new(),
+            inputs: Vec::new(),
+            input_names: Vec::new(),
+            rows: Vec::new(),
+            row_names: Vec::new(),
+        }
+    }
+}
+
+impl<F: PrimeField> R1csBuilder<F> {
+    pub fn new() -> Self {
+        R1csBuilder::default()
+    }
+
+    pub fn record<C: Circuit<F>>(circuit: C) -> Result<Self, ZkWordleError> {
+        let mut builder = R1csBuilder::new();
+        circuit
+            .synthesize(&mut builder)
+            .map_err(|e| ZkWordleError::CircuitConstruction(e.to_string()))?;
+        Ok(builder)
+    }
+
+    fn path(&self, name: String) -> String {
+        let mut path = self.namespace.join("/");
+        if !path.is_empty() {
+            path.push('/');
+        }
+        path.push_str(&name);
+        path
+    }
+
+    pub fn num_constraints(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.vars.len()
+    }
+
+    pub fn num_inputs(&self) -> usize {
+        self.inputs.len()
+    }
+
+    pub fn constraint_name(&self, row: usize) -> &str {
+        &self.row_names[row]
+    }
+
+    pub fn var_name(&self, var: usize) -> &str {
+        &self.var_names[var]
+    }
+
+    pub fn input_name(&self, input: usize) -> &str {
+        &self.input_names[input]
+    }
+
+    // Column of a variable in z = (vars, 1, inputs)
+    fn column(&self, var: Variable) -> usize {
+        match var.get_unchecked() {
+            Index::Aux(i) => i,
+            Index::Input(i) => self.vars.len() + i,
+        }
+    }
+
+    // The recorded values, or an error naming the first variable without one
+    fn values(&self) -> Result<(Vec<F>, Vec<F>), ZkWordleError> {
+        let collect = |values: &[Option<F>], names: &[String]| {
+            values
+                .iter()
+                .zip(names.iter())
+                .map(|(v, name)| v.ok_or_else(|| ZkWordleError::CircuitConstruction(format!("no value for {}", name))))
+                .collect::<Result<Vec<F>, _>>()
+        };
+        Ok((collect(&self.vars, &self.var_names)?, collect(&self.inputs, &self.input_names)?))
+    }
+
+    // Checks the values recorded during synthesis against every constraint
+    pub fn check(&self) -> Result<(), ZkWordleError> {
+        let (vars, inputs) = self.values()?;
+        self.check_assignment(&vars, &inputs)
+    }
+
+    // Evaluates A z, B z and C z row by row in plain field arithmetic and
+    // reports the first row where (A z) * (B z) != C z
+    pub fn check_assignment(&self, vars: &[F], inputs: &[F]) -> Result<(), ZkWordleError> {
+        if vars.len() != self.num_vars() || inputs.len() != self.num_inputs() {
+            return Err(ZkWordleError::CircuitConstruction(format!(
+                "expected {} variables and {} inputs, got {} and {}",
+                self.num_vars(),
+                self.num_inputs(),
+                vars.len(),
+                inputs.len()
+            )));
+        }
+
+        let mut z = vars.to_vec();
+        z.push(F::ONE);
+        z.extend_from_slice(inputs);
+        let eval = |lc: &LinearCombination<F>| {
+            lc.as_ref()
+                .iter()
+                .fold(F::ZERO, |acc, &(var, coeff)| acc + coeff * z[self.column(var)])
+        };
+
+        match self.rows.iter().position(|[a, b, c]| eval(a) * eval(b) != eval(c)) {
+            Some(index) => Err(ZkWordleError::UnsatisfiedConstraint {
+                index,
+                name: self.row_names[index].clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(feature = "spartan")]
+impl R1csBuilder<Scalar> {
+    // Appends one row of a matrix, merging repeated variables of the linear combination
+    fn push_row(&self, matrix: &mut Matrix, row: usize, lc: &LinearCombination<Scalar>) {
+        let mut merged: Vec<(usize, Scalar)> = Vec::new();
+        for &(var, coeff) in lc.as_ref() {
+            let col = self.column(var);
+            match merged.iter_mut().find(|(c, _)| *c == col) {
+                Some((_, acc)) => *acc += coeff,
+                None => merged.push((col, coeff)),
+            }
+        }
+        for (col, coeff) in merged.into_iter().filter(|(_, coeff)| *coeff != Scalar::ZERO) {
+            matrix.push((row, col, coeff.to_bytes()));
+        }
+    }
+
+    pub(crate) fn r1cs(&self) -> GameR1cs {
+        let mut matrices = [Vec::new(), Vec::new(), Vec::new()];
+        for (row, lcs) in self.rows.iter().enumerate() {
+            for (matrix, lc) in matrices.iter_mut().zip(lcs.iter()) {
+                self.push_row(matrix, row, lc);
+            }
+        }
+        let [a, b, c] = matrices;
+        GameR1cs {
+            num_cons: self.num_constraints(),
+            num_vars: self.num_vars(),
+            num_inputs: self.num_inputs(),
+            a,
+            b,
+            c,
+        }
+    }
+
+    pub fn instance(&self) -> Result<Instance, ZkWordleError> {
+        self.r1cs().instance()
+    }
+
+    // Entries in the densest of A, B and C, which sizes the SNARK generators
+    pub fn num_non_zero_entries(&self) -> usize {
+        self.r1cs().num_non_zero_entries()
+    }
+
+    // Witness variables and public inputs, or an error naming the first one without a value
+    pub fn assignment(&self) -> Result<(VarsAssignment, InputsAssignment), ZkWordleError> {
+        let (vars, inputs) = self.values()?;
+        let bytes = |values: Vec<Scalar>| values.iter().map(Scalar::to_bytes).collect::<Vec<_>>();
+        let vars = VarsAssignment::new(&bytes(vars)).map_err(circuit_error)?;
+        let inputs = InputsAssignment::new(&bytes(inputs)).map_err(circuit_error)?;
+        Ok((vars, inputs))
+    }
+}
+
+#[cfg(feature = "spartan")]
+fn circuit_error(err: R1CSError) -> ZkWordleError {
+    ZkWordleError::CircuitConstruction(format!("{:?}", err))
+}
+
+// Circuit constraints, whose shape the config fixes. WordleCircuit lists the
+// public inputs and witness variables.
+#[cfg(feature = "spartan")]
+pub(crate) fn game_constraints(config: &GameConfig) -> Result<GameR1cs, ZkWordleError> {
+    Ok(R1csBuilder::record(WordleCircuit::blank(config))?.r1cs())
+}
+
+// Witness variables and public inputs of one turn, in the layout of game_constraints
+#[cfg(feature = "spartan")]
+pub(crate) fn game_assignment(circuit: WordleCircuit<Scalar>) -> Result<(VarsAssignment, InputsAssignment), ZkWordleError> {
+    R1csBuilder::record(circuit)?.assignment()
+}
+
+#[cfg(all(test, feature = "spartan"))]
+mod tests {
+    use super::*;
+    use crate::commitment::Commitment;
+    use crate::feedback::tests::{letters, TRICKY_PAIRS};
+    use crate::feedback::LetterFeedback;
+    use crate::merkle::tests::answer_tree;
+    use bellman::gadgets::test::TestConstraintSystem;
+    use LetterFeedback::{Gray, Green};
+
+    fn circuit(hidden_word: &str, guess: &str, feedback: &[LetterFeedback]) -> WordleCircuit<Scalar> {
+        let config = GameConfig::default();
+        let path = answer_tree(&config, &[hidden_word]).path(&letters(hidden_word)).unwrap();
+        let hidden_word = letters(hidden_word);
+        let salt = Scalar::from(7u8);
+        let commitment = Commitment::new(&config, &hidden_word, &salt).field_element().unwrap();
+        WordleCircuit::for_turn(&config, &hidden_word, salt, commitment, &path, &letters(guess), feedback)
+    }
+
+    #[test]
+    fn builder_counts_and_names_what_it_records() {
+        let mut cs = R1csBuilder::new();
+        let x = cs.alloc(|| "x", || Ok(Scalar::from(3u8))).unwrap();
+        {
+            let mut cs = cs.namespace(|| "square");
+            let y = cs.alloc_input(|| "y", || Ok(Scalar::from(9u8))).unwrap();
+            cs.enforce(|| "x * x = y", |lc| lc + x, |lc| lc + x, |lc| lc + y);
+        }
+        let one = R1csBuilder::<Scalar>::one();
+        cs.enforce(|| "x + x = 2x", |lc| lc + x + x, |lc| lc + one, |lc| lc + (Scalar::from(2u8), x));
+
+        assert_eq!((cs.num_vars(), cs.num_inputs(), cs.num_constraints()), (1, 1, 2));
+        assert_eq!(cs.input_name(0), "square/y");
+        assert_eq!(cs.constraint_name(0), "square/x * x = y");
+
+        // repeated variables are merged into a single entry
+        let r1cs = cs.r1cs();
+        assert_eq!(r1cs.a.iter().filter(|(row, _, _)| *row == 1).count(), 1);
+
+        let (vars, inputs) = cs.assignment().unwrap();
+        let inst = cs.instance().unwrap();
+        assert!(inst.is_sat(&vars, &inputs).unwrap());
+        let wrong = InputsAssignment::new(&[Scalar::from(8u8).to_bytes()]).unwrap();
+        assert!(!inst.is_sat(&vars, &wrong).unwrap());
+
+        assert!(cs.check().is_ok());
+        match cs.check_assignment(&[Scalar::from(3u8)], &[Scalar::from(8u8)]) {
+            Err(ZkWordleError::UnsatisfiedConstraint { index, name }) => assert_eq!((index, name.as_str()), (0, "square/x * x = y")),
+            other => panic!("expected an unsatisfied constraint, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn recorded_instance_agrees_with_the_circuit() {
+        let inst = game_constraints(&GameConfig::default()).unwrap().instance().unwrap();
+        for (hidden_word, guess, expected) in TRICKY_PAIRS {
+            let mut wrong = expected.to_vec();
+            wrong[0] = if wrong[0] == Green { Gray } else { Green };
+
+            for feedback in [expected.to_vec(), wrong] {
+                let mut cs = TestConstraintSystem::<Scalar>::new();
+                circuit(hidden_word, guess, &feedback).synthesize(&mut cs).unwrap();
+
+                let (vars, inputs) = game_assignment(circuit(hidden_word, guess, &feedback)).unwrap();
+                assert_eq!(inst.is_sat(&vars, &inputs).unwrap(), cs.is_satisfied(), "{} against {}", guess, hidden_word);
+
+                let checked = R1csBuilder::record(circuit(hidden_word, guess, &feedback)).unwrap().check();
+                assert_eq!(checked.is_ok(), cs.is_satisfied(), "{} against {}", guess, hidden_word);
+            }
+        }
+    }
+
+    #[test]
+    fn check_names_the_first_failing_constraint() {
+        let builder = R1csBuilder::record(circuit("crane", "crane", &[Gray, Green, Green, Green, Green])).unwrap();
+        match builder.check() {
+            Err(ZkWordleError::UnsatisfiedConstraint { name, .. }) => assert_eq!(name, "eq 0 0/d * inv = 1 - out"),
+            other => panic!("expected an unsatisfied constraint, got {:?}", other),
+        }
+    }
+}
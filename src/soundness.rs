@@ -0,0 +1,291 @@
+// Soundness and completeness of whole games, run against every compiled-in backend.
+//
+// The adversary here is a cheating host: it knows its own word and salt and
+// can call ProofSystem::prove directly on any witness it likes, not just the
+// honest one Prover builds. Every such attempt has to be rejected by a
+// Verifier that only holds the commitment from the start of the game and the
+// root of the published answer list.
+
+use std::sync::Arc;
+
+use crate::circuit::WordleCircuit;
+use crate::commitment::Commitment;
+use crate::config::GameConfig;
+use crate::error::ZkWordleError;
+use crate::feedback::tests::letters;
+use crate::feedback::{Feedback, LetterFeedback};
+use crate::game::GameResult;
+use crate::guess::Guess;
+use crate::merkle::tests::answer_tree;
+use crate::merkle::WordTree;
+use crate::proof_system::ProofSystem;
+use crate::prover::{GuessProof, Prover};
+use crate::verifier::{GameVerifier, Verifier};
+use LetterFeedback::{Gray, Green, Yellow};
+
+// Sample of dictionary words, heavy on repeated letters, which is also the
+// answer list of every game here
+const WORDS: [&str; 12] = [
+    "crane", "eerie", "geese", "speed", "abbey", "babes", "kebab", "robot", "floor", "mummy", "sissy", "lever",
+];
+
+// The classic game, over a shallow answer tree: every level of the tree is
+// the same few constraints, and a deep one only slows the games down
+fn config() -> GameConfig {
+    GameConfig {
+        answer_tree_depth: 4,
+        ..GameConfig::default()
+    }
+}
+
+fn answers<S: ProofSystem>() -> WordTree<S::Field> {
+    answer_tree(&config(), &WORDS)
+}
+
+// A committed game the adversary controls, salt included
+struct CheatingHost<S: ProofSystem> {
+    config: GameConfig,
+    answers: WordTree<S::Field>,
+    hidden_word: Vec<u8>,
+    salt: S::Field,
+    commitment: Commitment,
+}
+
+impl<S: ProofSystem> CheatingHost<S> {
+    fn new(word: &str) -> Self {
+        let hidden_word = letters(word);
+        let salt = S::Field::from(0x5eed);
+        let config = config();
+        let commitment = Commitment::new(&config, &hidden_word, &salt);
+        CheatingHost {
+            config,
+            answers: answers::<S>(),
+            hidden_word,
+            salt,
+            commitment,
+        }
+    }
+
+    fn verifier(&self, params: &S::Params) -> Verifier<S> {
+        Verifier::new(&self.config, self.commitment, self.answers.root(), S::verifying_key(params))
+    }
+
+    // Proves the claimed feedback for whatever word it likes, against its
+    // original commitment. A word outside the answers has no path of its own,
+    // so it borrows the first answer's.
+    fn forge(&self, params: &S::Params, hidden_word: &[u8], guess: &Guess, feedback: &[LetterFeedback]) -> GuessProof {
+        let path = self
+            .answers
+            .path(hidden_word)
+            .unwrap_or_else(|| self.answers.path(&self.answers.words()[0]).unwrap());
+        let circuit = WordleCircuit::for_turn(
+            &self.config,
+            hidden_word,
+            self.salt,
+            self.commitment.field_element().unwrap(),
+            &path,
+            guess.letters(),
+            feedback,
+        );
+        let proof = S::prove(params, circuit).unwrap();
+        GuessProof::from_bytes(S::proof_to_bytes(&proof).unwrap())
+    }
+}
+
+fn guess(word: &str) -> Guess {
+    Guess::parse(&config(), word).unwrap()
+}
+
+fn accepts<S: ProofSystem>(verifier: &Verifier<S>, guess: &Guess, feedback: &[LetterFeedback], proof: &GuessProof) -> bool {
+    // Err (malformed proof) counts as a rejection as much as Ok(false)
+    matches!(verifier.verify(guess, &Feedback::from_letters(feedback.to_vec()), proof), Ok(true))
+}
+
+fn honest_proofs_verify<S: ProofSystem>(params: &Arc<S::Params>) {
+    let config = config();
+    let answers = answers::<S>();
+    for (i, hidden_word) in WORDS.iter().enumerate() {
+        let prover = Prover::<S>::new(&config, &answers, letters(hidden_word), params.clone()).unwrap();
+        let verifier = Verifier::<S>::new(&config, prover.commitment(), answers.root(), S::verifying_key(params));
+
+        // every hidden word against its neighbour and itself
+        for guessed in [WORDS[(i + 1) % WORDS.len()], hidden_word] {
+            let guess = guess(guessed);
+            let (feedback, proof) = prover.prove(&guess).unwrap();
+            assert!(
+                accepts(&verifier, &guess, feedback.letters(), &proof),
+                "{}: honest proof for {} against {} rejected",
+                S::NAME,
+                guessed,
+                hidden_word
+            );
+        }
+    }
+}
+
+fn lying_feedback_is_rejected<S: ProofSystem>(params: &Arc<S::Params>) {
+    let host = CheatingHost::<S>::new("eerie");
+    let verifier = host.verifier(params);
+    let guess = guess("geese");
+    let honest = Feedback::score(&host.hidden_word, guess.letters());
+    let honest_proof = host.forge(params, &host.hidden_word, &guess, honest.letters());
+    assert!(accepts(&verifier, &guess, honest.letters(), &honest_proof));
+
+    for i in 0..honest.letters().len() {
+        for wrong in [Gray, Yellow, Green].into_iter().filter(|&f| f != honest.letters()[i]) {
+            let mut lie = honest.letters().to_vec();
+            lie[i] = wrong;
+
+            // claiming the lie next to an honest proof
+            assert!(!accepts(&verifier, &guess, &lie, &honest_proof), "{}: relabelled {:?}", S::NAME, lie);
+
+            // proving the lie from an unsatisfied witness
+            let forged = host.forge(params, &host.hidden_word, &guess, &lie);
+            assert!(!accepts(&verifier, &guess, &lie, &forged), "{}: forged {:?}", S::NAME, lie);
+        }
+    }
+}
+
+fn swapped_word_is_rejected<S: ProofSystem>(params: &Arc<S::Params>) {
+    let config = config();
+    let host = CheatingHost::<S>::new("crane");
+    let verifier = host.verifier(params);
+    let guess = guess("robot");
+
+    // mid-game the host starts scoring against another word, keeping the old commitment
+    let swapped = letters("floor");
+    let feedback = Feedback::score(&swapped, guess.letters());
+    let forged = host.forge(params, &swapped, &guess, feedback.letters());
+    assert!(!accepts(&verifier, &guess, feedback.letters(), &forged), "{}: swapped word", S::NAME);
+
+    // or simply proves honestly from a fresh game with the other word
+    let other = Prover::<S>::new(&config, &host.answers, swapped, params.clone()).unwrap();
+    let (feedback, proof) = other.prove(&guess).unwrap();
+    assert!(!accepts(&verifier, &guess, feedback.letters(), &proof), "{}: fresh game", S::NAME);
+}
+
+fn reused_proof_is_rejected<S: ProofSystem>(params: &Arc<S::Params>) {
+    let config = config();
+    let answers = answers::<S>();
+    let prover = Prover::<S>::new(&config, &answers, letters("crane"), params.clone()).unwrap();
+    let verifier = Verifier::<S>::new(&config, prover.commitment(), answers.root(), S::verifying_key(params));
+
+    // "fight" and "pious" share every letter's feedback (all gray), so only the
+    // guess letters bound into the proof tell them apart
+    let (feedback, proof) = prover.prove(&guess("fight")).unwrap();
+    assert_eq!(feedback, Feedback::score(&letters("crane"), guess("pious").letters()));
+    assert!(!accepts(&verifier, &guess("pious"), feedback.letters(), &proof), "{}: other guess", S::NAME);
+
+    // and a proof from one game says nothing about another game with the same word
+    let rematch = Prover::<S>::new(&config, &answers, letters("crane"), params.clone()).unwrap();
+    let rematch_verifier =
+        Verifier::<S>::new(&config, rematch.commitment(), answers.root(), S::verifying_key(params));
+    assert!(!accepts(&rematch_verifier, &guess("fight"), feedback.letters(), &proof), "{}: other game", S::NAME);
+}
+
+fn reveal_is_bound_to_the_commitment<S: ProofSystem>(params: &Arc<S::Params>) {
+    let config = config();
+    let answers = answers::<S>();
+    let prover = Prover::<S>::new(&config, &answers, letters("crane"), params.clone()).unwrap();
+    let verifier = Verifier::<S>::new(&config, prover.commitment(), answers.root(), S::verifying_key(params));
+    let (word, proof) = prover.reveal().unwrap();
+    assert_eq!(word.to_string(), "crane");
+    assert!(verifier.verify_reveal(&word, &proof).unwrap(), "{}: honest reveal", S::NAME);
+    assert!(!verifier.verify_reveal(&guess("crate"), &proof).unwrap(), "{}: other word", S::NAME);
+
+    // a host disclosing another word than the committed one
+    let host = CheatingHost::<S>::new("crane");
+    let verifier = host.verifier(params);
+    let swapped = guess("slate");
+    let forged = host.forge(params, swapped.letters(), &swapped, &[Green; 5]);
+    assert!(!verifier.verify_reveal(&swapped, &forged).unwrap_or(false), "{}: swapped reveal", S::NAME);
+}
+
+fn hidden_word_outside_the_answers_is_rejected<S: ProofSystem>(params: &Arc<S::Params>) {
+    // a word nobody can guess, so the player could never win
+    let config = config();
+    let answers = answers::<S>();
+    assert!(Prover::<S>::new(&config, &answers, letters("zzzzz"), params.clone()).is_err());
+
+    // proving from a tree of the host's own making, which does hold the word
+    let rigged = answer_tree(&config, &[&WORDS[..], &["zzzzz"]].concat());
+    let prover = Prover::<S>::new(&config, &rigged, letters("zzzzz"), params.clone()).unwrap();
+    let verifier = Verifier::<S>::new(&config, prover.commitment(), answers.root(), S::verifying_key(params));
+    let (feedback, proof) = prover.prove(&guess("crane")).unwrap();
+    assert!(!accepts(&verifier, &guess("crane"), feedback.letters(), &proof), "{}: rigged tree", S::NAME);
+
+    // or from the published tree, with the path of an actual answer
+    let host = CheatingHost::<S>::new("zzzzz");
+    let feedback = Feedback::score(&host.hidden_word, guess("crane").letters());
+    let forged = host.forge(params, &host.hidden_word, &guess("crane"), feedback.letters());
+    let verifier = host.verifier(params);
+    assert!(!accepts(&verifier, &guess("crane"), feedback.letters(), &forged), "{}: borrowed path", S::NAME);
+}
+
+fn switching_words_mid_game_is_caught<S: ProofSystem>(params: &Arc<S::Params>) {
+    let config = config();
+    let answers = answers::<S>();
+    let prover = Prover::<S>::new(&config, &answers, letters("crane"), params.clone()).unwrap();
+    let mut player = GameVerifier::<S>::new(&config, prover.commitment(), answers.root(), S::verifying_key(params));
+    let (feedback, proof) = prover.prove(&guess("robot")).unwrap();
+    assert!(player.verify_turn(&prover.commitment(), &guess("robot"), &feedback, &proof).unwrap());
+
+    // Absurdle-style, the host moves to a word the guesses so far fit just as
+    // well, and keeps answering from there under a fresh commitment
+    let switched = Prover::<S>::new(&config, &answers, letters("eerie"), params.clone()).unwrap();
+    assert_eq!(Feedback::score(&letters("eerie"), guess("robot").letters()), feedback);
+    let (feedback, proof) = switched.prove(&guess("crane")).unwrap();
+    assert!(matches!(
+        player.verify_turn(&switched.commitment(), &guess("crane"), &feedback, &proof),
+        Err(ZkWordleError::CommitmentMismatch)
+    ));
+
+    // or claims the proof is for the old commitment after all
+    assert!(!player.verify_turn(&prover.commitment(), &guess("crane"), &feedback, &proof).unwrap_or(false));
+
+    // or proves the switched word against the old commitment
+    let host = CheatingHost::<S>::new("crane");
+    let mut player = GameVerifier::<S>::new(&config, host.commitment, answers.root(), S::verifying_key(params));
+    let honest = Feedback::score(&host.hidden_word, guess("robot").letters());
+    let proof = host.forge(params, &host.hidden_word, &guess("robot"), honest.letters());
+    assert!(player.verify_turn(&host.commitment, &guess("robot"), &honest, &proof).unwrap());
+    let switched = Feedback::score(&letters("eerie"), guess("crane").letters());
+    let forged = host.forge(params, &letters("eerie"), &guess("crane"), switched.letters());
+    assert!(!player.verify_turn(&host.commitment, &guess("crane"), &switched, &forged).unwrap_or(false));
+
+    // none of which counts as a turn, and the honest game goes on
+    assert_eq!(player.game().guesses(), 1);
+    let feedback = Feedback::score(&host.hidden_word, guess("crane").letters());
+    let proof = host.forge(params, &host.hidden_word, &guess("crane"), feedback.letters());
+    assert!(player.verify_turn(&host.commitment, &guess("crane"), &feedback, &proof).unwrap(), "{}", S::NAME);
+    assert_eq!(player.game().result(), Some(GameResult::Won { guesses: 2 }));
+}
+
+fn sound_and_complete<S: ProofSystem>() {
+    let params = Arc::new(S::setup(&config()).unwrap());
+    honest_proofs_verify::<S>(&params);
+    lying_feedback_is_rejected::<S>(&params);
+    swapped_word_is_rejected::<S>(&params);
+    reused_proof_is_rejected::<S>(&params);
+    reveal_is_bound_to_the_commitment::<S>(&params);
+    hidden_word_outside_the_answers_is_rejected::<S>(&params);
+    switching_words_mid_game_is_caught::<S>(&params);
+}
+
+#[cfg(feature = "spartan")]
+#[test]
+fn spartan_snark_is_sound_and_complete() {
+    sound_and_complete::<crate::spartan::SpartanSnark>();
+}
+
+#[cfg(feature = "spartan")]
+#[test]
+fn spartan_nizk_is_sound_and_complete() {
+    sound_and_complete::<crate::spartan::SpartanNizk>();
+}
+
+#[cfg(feature = "groth16")]
+#[test]
+fn groth16_is_sound_and_complete() {
+    sound_and_complete::<crate::groth16::Groth16>();
+}
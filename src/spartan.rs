@@ -0,0 +1,372 @@
+// Spartan backends over the Ristretto scalar field.
+//
+// Setup is transparent for both: generators (and, for the SNARK, the
+// commitment to the encoded circuit) are derived from the R1CS matrices alone,
+// which is also all the params file stores. The SNARK pays for encoding the
+// circuit once so that every later proof verifies in sublinear time; the NIZK
+// skips that preprocessing, which suits one-off proofs such as an end-of-game
+// reveal, at the price of larger proofs and verification linear in the circuit.
+
+use bincode::Options;
+use curve25519_dalek::ristretto::CompressedRistretto;
+use curve25519_dalek::scalar::Scalar;
+use libspartan::{InputsAssignment, NIZK, SNARK};
+use merlin::Transcript;
+use serde::Deserialize;
+use std::io::{Read, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::circuit::WordleCircuit;
+use crate::config::GameConfig;
+use crate::error::ZkWordleError;
+use crate::keys::{NizkKey, ProvingKey, VerifyingKey};
+use crate::params::{params_path, PublicParams};
+use crate::proof_system::ProofSystem;
+use crate::r1cs::{game_assignment, GameR1cs};
+
+pub struct SpartanSnark;
+
+pub struct SpartanNizk;
+
+// Proofs arrive from the untrusted host. Honest ones for the Wordle circuit are
+// far below this size; the limit keeps a crafted length prefix from making the
+// decoder allocate more than that.
+const MAX_PROOF_BYTES: u64 = 1 << 20;
+
+fn proof_encoding() -> impl Options {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .with_limit(MAX_PROOF_BYTES)
+}
+
+fn circuit_error<E: std::fmt::Debug>(err: E) -> ZkWordleError {
+    ZkWordleError::CircuitConstruction(format!("{:?}", err))
+}
+
+fn public_assignment(inputs: &[Scalar]) -> Result<InputsAssignment, ZkWordleError> {
+    let inputs: Vec<[u8; 32]> = inputs.iter().map(Scalar::to_bytes).collect();
+    InputsAssignment::new(&inputs).map_err(circuit_error)
+}
+
+// A proof that decodes can still carry vectors of the wrong length, which
+// libspartan asserts on rather than reporting, and its proofs keep their
+// fields private. These mirror the part both proofs start with, the R1CS
+// satisfiability proof, as libspartan 0.8 serializes it, so that check_shape
+// can find those lengths first. Points and scalars are 32 bytes each.
+type Element = [u8; 32];
+
+#[derive(Deserialize)]
+struct SumcheckLayout {
+    comm_polys: Vec<Element>,
+    comm_evals: Vec<Element>,
+    proofs: Vec<DotProductLayout>,
+}
+
+#[derive(Deserialize)]
+struct DotProductLayout {
+    _delta: Element,
+    _beta: Element,
+    z: Vec<Element>,
+    _z_delta: Element,
+    _z_beta: Element,
+}
+
+#[derive(Deserialize)]
+struct SatProofLayout {
+    comm_vars: Vec<Element>,
+    sc_proof_phase1: SumcheckLayout,
+    // claims of phase 2 with their proofs of knowledge and of the product,
+    // then the equality proof closing phase 1
+    _claims_phase2: ([Element; 4], [Element; 3], [Element; 8], [Element; 2]),
+    sc_proof_phase2: SumcheckLayout,
+    _comm_vars_at_ry: Element,
+    bullet_l: Vec<Element>,
+    bullet_r: Vec<Element>,
+    // the rest of the evaluation proof, then the equality proof closing phase 2
+    _rest: [Element; 6],
+}
+
+#[derive(Deserialize)]
+struct NizkLayout {
+    sat_proof: SatProofLayout,
+    r: (Vec<Element>, Vec<Element>),
+}
+
+impl SumcheckLayout {
+    fn fits(&self, num_rounds: usize, degree: usize) -> bool {
+        self.comm_polys.len() == num_rounds
+            && self.comm_evals.len() == num_rounds
+            && self.proofs.len() == num_rounds
+            && self.proofs.iter().all(|proof| proof.z.len() == degree + 1)
+    }
+}
+
+// Rounds of the two sum-checks and variables of the witness polynomial, for
+// the instance as libspartan pads it: constraints and variables (at least one
+// more than the inputs) each to a power of two
+fn rounds(r1cs: &GameR1cs) -> (usize, usize, usize) {
+    let num_cons = r1cs.num_cons.max(2).next_power_of_two();
+    let num_vars = r1cs.num_vars.max(r1cs.num_inputs + 1).next_power_of_two();
+    let log = |n: usize| n.trailing_zeros() as usize;
+    (log(num_cons), log(2 * num_vars), log(num_vars))
+}
+
+impl SatProofLayout {
+    // The witness commitment has a row per value of the first half of the
+    // variables, and its evaluation proof a round per variable of the second
+    fn fits(&self, r1cs: &GameR1cs) -> bool {
+        let (rounds_x, rounds_y, vars) = rounds(r1cs);
+        self.comm_vars.len() == 1 << (vars / 2)
+            && self.comm_vars.iter().all(|point| CompressedRistretto(*point).decompress().is_some())
+            && self.sc_proof_phase1.fits(rounds_x, 3)
+            && self.sc_proof_phase2.fits(rounds_y, 2)
+            && self.bullet_l.len() == vars - vars / 2
+            && self.bullet_r.len() == vars - vars / 2
+    }
+}
+
+fn check_shape<'a, L: Deserialize<'a>>(bytes: &'a [u8], fits: impl FnOnce(&L) -> bool) -> Result<(), ZkWordleError> {
+    let layout = proof_encoding()
+        .allow_trailing_bytes()
+        .deserialize(bytes)
+        .map_err(|e| ZkWordleError::MalformedProof(e.to_string()))?;
+    if !fits(&layout) {
+        return Err(ZkWordleError::MalformedProof("proof does not fit the circuit".to_string()));
+    }
+    Ok(())
+}
+
+// Backstop for the asserts check_shape does not reach, as in the SNARK's
+// proof of the circuit evaluations. Only works with panic = "unwind", the
+// default; with panic = "abort" such a proof still ends the process.
+fn verify_unwind<F: FnOnce() -> bool>(verify: F) -> Result<bool, ZkWordleError> {
+    panic::catch_unwind(AssertUnwindSafe(verify))
+        .map_err(|_| ZkWordleError::MalformedProof("proof has an inconsistent shape".to_string()))
+}
+
+impl ProofSystem for SpartanSnark {
+    const NAME: &'static str = "spartan";
+
+    type Field = Scalar;
+    type Params = ProvingKey;
+    type VerifyingKey = VerifyingKey;
+    type Proof = SNARK;
+
+    fn params_path(config: &GameConfig) -> PathBuf {
+        params_path(config)
+    }
+
+    fn setup(config: &GameConfig) -> Result<ProvingKey, ZkWordleError> {
+        ProvingKey::generate(config)
+    }
+
+    fn verifying_key(params: &ProvingKey) -> Arc<VerifyingKey> {
+        params.verifying_key()
+    }
+
+    fn prove(params: &ProvingKey, circuit: WordleCircuit<Scalar>) -> Result<SNARK, ZkWordleError> {
+        let (vars, inputs) = game_assignment(circuit)?;
+        let mut prover_transcript = Transcript::new(b"zk_wordle");
+        Ok(SNARK::prove(
+            &params.inst,
+            &params.vk.comm,
+            &params.decomm,
+            vars,
+            &inputs,
+            &params.vk.gens,
+            &mut prover_transcript,
+        ))
+    }
+
+    fn verify(key: &VerifyingKey, inputs: &[Scalar], proof: &SNARK) -> Result<bool, ZkWordleError> {
+        let bytes = SpartanSnark::proof_to_bytes(proof)?;
+        check_shape(&bytes, |layout: &SatProofLayout| layout.fits(&key.r1cs))?;
+        let inputs = public_assignment(inputs)?;
+        let mut verifier_transcript = Transcript::new(b"zk_wordle");
+        verify_unwind(|| {
+            proof
+                .verify(&key.comm, &inputs, &mut verifier_transcript, &key.gens)
+                .is_ok()
+        })
+    }
+
+    fn write_params<W: Write>(params: &ProvingKey, writer: W) -> Result<(), ZkWordleError> {
+        SpartanSnark::write_verifying_key(&params.vk, writer)
+    }
+
+    fn read_params<R: Read>(config: &GameConfig, reader: R) -> Result<ProvingKey, ZkWordleError> {
+        PublicParams::read_from(config, reader)?.proving_key()
+    }
+
+    fn write_verifying_key<W: Write>(key: &VerifyingKey, writer: W) -> Result<(), ZkWordleError> {
+        PublicParams::from_r1cs(&key.config, key.r1cs.clone())?.write_to(writer)
+    }
+
+    fn read_verifying_key<R: Read>(config: &GameConfig, reader: R) -> Result<VerifyingKey, ZkWordleError> {
+        PublicParams::read_from(config, reader)?.verifying_key()
+    }
+
+    fn proof_to_bytes(proof: &SNARK) -> Result<Vec<u8>, ZkWordleError> {
+        proof_encoding().serialize(proof).map_err(ZkWordleError::Serialization)
+    }
+
+    fn proof_from_bytes(bytes: &[u8]) -> Result<SNARK, ZkWordleError> {
+        proof_encoding()
+            .deserialize(bytes)
+            .map_err(|e| ZkWordleError::MalformedProof(e.to_string()))
+    }
+}
+
+impl ProofSystem for SpartanNizk {
+    const NAME: &'static str = "spartan-nizk";
+
+    type Field = Scalar;
+    type Params = Arc<NizkKey>;
+    type VerifyingKey = NizkKey;
+    type Proof = NIZK;
+
+    // Same file as the SNARK, which holds nothing but the circuit
+    fn params_path(config: &GameConfig) -> PathBuf {
+        params_path(config)
+    }
+
+    fn setup(config: &GameConfig) -> Result<Arc<NizkKey>, ZkWordleError> {
+        Ok(Arc::new(NizkKey::generate(config)?))
+    }
+
+    fn verifying_key(params: &Arc<NizkKey>) -> Arc<NizkKey> {
+        params.clone()
+    }
+
+    fn prove(params: &Arc<NizkKey>, circuit: WordleCircuit<Scalar>) -> Result<NIZK, ZkWordleError> {
+        let (vars, inputs) = game_assignment(circuit)?;
+        let mut prover_transcript = Transcript::new(b"zk_wordle");
+        Ok(NIZK::prove(&params.inst, vars, &inputs, &params.gens, &mut prover_transcript))
+    }
+
+    fn verify(key: &NizkKey, inputs: &[Scalar], proof: &NIZK) -> Result<bool, ZkWordleError> {
+        let bytes = SpartanNizk::proof_to_bytes(proof)?;
+        check_shape(&bytes, |layout: &NizkLayout| {
+            let (rounds_x, rounds_y, _) = rounds(&key.r1cs);
+            layout.sat_proof.fits(&key.r1cs) && layout.r.0.len() == rounds_x && layout.r.1.len() == rounds_y
+        })?;
+        let inputs = public_assignment(inputs)?;
+        let mut verifier_transcript = Transcript::new(b"zk_wordle");
+        verify_unwind(|| {
+            proof
+                .verify(&key.inst, &inputs, &mut verifier_transcript, &key.gens)
+                .is_ok()
+        })
+    }
+
+    fn write_params<W: Write>(params: &Arc<NizkKey>, writer: W) -> Result<(), ZkWordleError> {
+        SpartanNizk::write_verifying_key(params, writer)
+    }
+
+    fn read_params<R: Read>(config: &GameConfig, reader: R) -> Result<Arc<NizkKey>, ZkWordleError> {
+        Ok(Arc::new(SpartanNizk::read_verifying_key(config, reader)?))
+    }
+
+    fn write_verifying_key<W: Write>(key: &NizkKey, writer: W) -> Result<(), ZkWordleError> {
+        PublicParams::from_r1cs(&key.config, key.r1cs.clone())?.write_to(writer)
+    }
+
+    fn read_verifying_key<R: Read>(config: &GameConfig, reader: R) -> Result<NizkKey, ZkWordleError> {
+        PublicParams::read_from(config, reader)?.nizk_key()
+    }
+
+    fn proof_to_bytes(proof: &NIZK) -> Result<Vec<u8>, ZkWordleError> {
+        proof_encoding().serialize(proof).map_err(ZkWordleError::Serialization)
+    }
+
+    fn proof_from_bytes(bytes: &[u8]) -> Result<NIZK, ZkWordleError> {
+        proof_encoding()
+            .deserialize(bytes)
+            .map_err(|e| ZkWordleError::MalformedProof(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::Alphabet;
+    use crate::feedback::tests::letters;
+    use crate::guess::Guess;
+    use crate::merkle::tests::answer_tree;
+    use crate::prover::{GuessProof, Prover};
+    use crate::verifier::Verifier;
+
+    fn proves_and_verifies<S: ProofSystem>() {
+        let config = GameConfig::default();
+        let params = Arc::new(S::setup(&config).unwrap());
+        let answers = answer_tree(&config, &["crane", "eerie"]);
+        let prover = Prover::<S>::new(&config, &answers, letters("eerie"), params.clone()).unwrap();
+        let verifier = Verifier::<S>::new(&config, prover.commitment(), answers.root(), S::verifying_key(&params));
+
+        let guess = Guess::parse(&config, "geese").unwrap();
+        let (feedback, proof) = prover.prove(&guess).unwrap();
+        assert!(verifier.verify(&guess, &feedback, &proof).unwrap());
+
+        let other = Guess::parse(&config, "crane").unwrap();
+        assert!(!verifier.verify(&other, &feedback, &proof).unwrap());
+
+        // wherever a length prefix sits among the leading fields, blowing it
+        // up must neither panic nor allocate past the limit
+        let bytes = proof.as_bytes();
+        let mut inflated = bytes.to_vec();
+        for i in 0..bytes.len().saturating_sub(8).min(256) {
+            inflated[i..i + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+            let _ = S::proof_from_bytes(&inflated);
+            inflated[i..i + 8].copy_from_slice(&bytes[i..i + 8]);
+        }
+        assert!(S::proof_from_bytes(&u64::MAX.to_le_bytes()).is_err());
+        assert!(S::proof_from_bytes(&[bytes, &[0]].concat()).is_err());
+
+        // one row short in the witness commitment, the first vector of
+        // either proof, still decodes but is caught before libspartan sees it
+        let rows = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+        let short = [&(rows - 1).to_le_bytes(), &bytes[8 + 32..]].concat();
+        match verifier.verify(&guess, &feedback, &GuessProof::from_bytes(short)) {
+            Err(ZkWordleError::MalformedProof(reason)) => assert_eq!(reason, "proof does not fit the circuit"),
+            other => panic!("short proof gave {:?}", other),
+        }
+    }
+
+    #[test]
+    fn snark_proves_and_verifies_a_turn() {
+        proves_and_verifies::<SpartanSnark>();
+    }
+
+    #[test]
+    fn nizk_proves_and_verifies_a_turn() {
+        proves_and_verifies::<SpartanNizk>();
+    }
+
+    #[test]
+    fn every_variant_has_its_own_keys() {
+        let six = GameConfig::new(6, Alphabet::english(), Some(6)).unwrap();
+        for (word_len, alphabet, hidden_word, guess) in [
+            (4, Alphabet::english(), "tree", "rote"),
+            (7, Alphabet::english(), "cabbage", "baggage"),
+            (5, Alphabet::greek(), "λόγος", "σοφία"),
+        ] {
+            let config = GameConfig::new(word_len, alphabet, Some(6)).unwrap();
+            let params = Arc::new(SpartanSnark::setup(&config).unwrap());
+            let answers = answer_tree(&config, &[hidden_word]);
+            let hidden_word = config.alphabet.split(hidden_word).unwrap();
+            let prover = Prover::<SpartanSnark>::new(&config, &answers, hidden_word, params.clone()).unwrap();
+            let key = SpartanSnark::verifying_key(&params);
+            let verifier = Verifier::<SpartanSnark>::new(&config, prover.commitment(), answers.root(), key);
+
+            let guess = Guess::parse(&config, guess).unwrap();
+            let (feedback, proof) = prover.prove(&guess).unwrap();
+            assert_eq!(feedback.letters().len(), word_len);
+            assert!(verifier.verify(&guess, &feedback, &proof).unwrap());
+
+            // a guess for a game with longer words does not fit
+            assert!(prover.prove(&Guess::parse(&six, "frozen").unwrap()).is_err());
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use std::sync::Arc;
+
+use crate::circuit::public_inputs;
+use crate::commitment::Commitment;
+use crate::config::GameConfig;
+use crate::error::ZkWordleError;
+use crate::feedback::{Feedback, LetterFeedback};
+use crate::game::{Game, GameResult};
+use crate::guess::{check_letters, Guess};
+use crate::merkle::MerkleRoot;
+use crate::proof_system::ProofSystem;
+use crate::prover::GuessProof;
+
+// Player side of the game. Only sees the commitment published at game start,
+// never the word, and the root of the answer list the word was drawn from.
+pub struct Verifier<S: ProofSystem> {
+    config: GameConfig,
+    commitment: Commitment,
+    answers_root: MerkleRoot,
+    key: Arc<S::VerifyingKey>,
+}
+
+impl<S: ProofSystem> Verifier<S> {
+    pub fn new(
+        config: &GameConfig,
+        commitment: Commitment,
+        answers_root: MerkleRoot,
+        key: Arc<S::VerifyingKey>,
+    ) -> Self {
+        Verifier {
+            config: config.clone(),
+            commitment,
+            answers_root,
+            key,
+        }
+    }
+
+    // Ok(false) means the proof decoded but does not verify; Err means the
+    // statement or proof could not even be checked. With the Spartan backends
+    // that takes panic = "unwind", the default: libspartan asserts on some
+    // malformed proofs, and only unwinding turns those into errors.
+    pub fn verify(&self, guess: &Guess, feedback: &Feedback, proof: &GuessProof) -> Result<bool, ZkWordleError> {
+        check_letters(&self.config, guess.letters()).map_err(ZkWordleError::InvalidGuess)?;
+        if feedback.letters().len() != self.config.word_len {
+            return Err(ZkWordleError::InvalidFeedback(format!(
+                "expected feedback for {} letters, got {}",
+                self.config.word_len,
+                feedback.letters().len()
+            )));
+        }
+
+        let commitment = self.commitment.field_element()?;
+        let answers_root = self.answers_root.field_element()?;
+        let proof = S::proof_from_bytes(proof.as_bytes())?;
+        let inputs = public_inputs(commitment, answers_root, guess.letters(), feedback.letters());
+        S::verify(&self.key, &inputs, &proof)
+    }
+
+    // Checks the word disclosed at the end of the game against the commitment
+    pub fn verify_reveal(&self, word: &Guess, proof: &GuessProof) -> Result<bool, ZkWordleError> {
+        let all_green = Feedback::from_letters(vec![LetterFeedback::Green; self.config.word_len]);
+        self.verify(word, &all_green, proof)
+    }
+}
+
+// Player side of a whole game: checks every turn against the commitment
+// published at the start and keeps the score. A host that switches words
+// mid-game has to either announce a new commitment, which is turned down
+// before its proof is even looked at, or prove against the old one, which
+// fails. Rejected turns do not count.
+pub struct GameVerifier<S: ProofSystem> {
+    verifier: Verifier<S>,
+    game: Game,
+}
+
+impl<S: ProofSystem> GameVerifier<S> {
+    pub fn new(
+        config: &GameConfig,
+        commitment: Commitment,
+        answers_root: MerkleRoot,
+        key: Arc<S::VerifyingKey>,
+    ) -> Self {
+        GameVerifier {
+            verifier: Verifier::new(config, commitment, answers_root, key),
+            game: Game::new(config),
+        }
+    }
+
+    pub fn commitment(&self) -> Commitment {
+        self.verifier.commitment
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    // Checks the host's answer to the next guess, which it says it proved
+    // against commitment, and counts the turn if the proof holds. Ok(false)
+    // is a proof that does not verify against the game's commitment.
+    pub fn verify_turn(
+        &mut self,
+        commitment: &Commitment,
+        guess: &Guess,
+        feedback: &Feedback,
+        proof: &GuessProof,
+    ) -> Result<bool, ZkWordleError> {
+        if self.game.is_over() {
+            return Err(ZkWordleError::GameOver);
+        }
+        if *commitment != self.verifier.commitment {
+            return Err(ZkWordleError::CommitmentMismatch);
+        }
+        if !self.verifier.verify(guess, feedback, proof)? {
+            return Ok(false);
+        }
+        self.game.record(feedback)?;
+        Ok(true)
+    }
+
+    // Stops the game early, as a loss
+    pub fn give_up(&mut self) -> GameResult {
+        self.game.give_up()
+    }
+
+    pub fn verify_reveal(&self, word: &Guess, proof: &GuessProof) -> Result<bool, ZkWordleError> {
+        self.verifier.verify_reveal(word, proof)
+    }
+}